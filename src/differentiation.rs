@@ -7,11 +7,11 @@ fn instructions() -> helper::LongPage {
 	helper::LongPage {
 		title: "DIFFERENTIATION".to_string(),
 		links: calculus::links(2),
-		instructions: "In the url bar after <tt>https://basic-calculus.herokuapp.com/differentiation</tt> type the following:<p align=center><tt>&sol;&lt;value of <i>x</i> at which to calculate function and derivatives&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>".to_string(),
+		instructions: "In the url bar after <tt>https://basic-calculus.herokuapp.com/differentiation</tt> type the following:<p align=center><tt>&sol;&lt;value of <i>x</i> at which to calculate function and derivatives&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>By default the function and its first three derivatives are calculated.  To calculate derivatives through some other order <i>n</i> (no greater than 10), append <tt>?order=&lt;n&gt;</tt> to the url.  For a function which is analytic (ie, one which is composed of functions that are smooth for complex arguments), its first derivative may be calculated to nearly machine precision by appending <tt>?method=complex</tt> instead, in which case only the function and that derivative are calculated.  (Separate the two parameters with <tt>&amp;</tt> if both are used.)  Values from the left and right of the point are also calculated, so that a jump, corner, or cusp may be detected, and if the point lies at the edge of the function's domain only the side within the domain is used.<br>To tabulate the function and its first two derivatives at equally spaced points, instead type the following:<p align=center><tt>&sol;table&sol;&lt;smallest value of <i>x</i>&gt;&sol;&lt;largest value of <i>x</i>&gt;&sol;&lt;number of intervals (no more than 1000)&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>".to_string(),
 		note: format!("{}{}{}", helper::NOTE1, "", helper::NOTE2),
-		example: "To differentiate the function 2<i>x</i> + 3/(<i>x</i><sup>4</sup> + 5) at <i>x</i> = 1, type <tt>/1/2x+3d(x**4+5)</tt> after the current url address. The results for the values of the function and of its first three derivatives should be <tt>2.5, 1.66..., -0.55..., and 1.11...</tt>".to_string(),
-		algorithm: "finite differences for small values of &Delta;<i>x</i>, excluding any reference to the particular point itself in the case of a removable singularity".to_string(),
-		json: "Type '/json' in the url bar immediately after 'differentiation' if you would like the result in this format rather than html.  A successful response will contain three properties: 'x' (a float), 'nonsingular' (a boolean reflecting whether or not the function has a removable singularity), and 'derivs' (a 4-element array of floats whose values represent the function value and first through third derivatives, respectively).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+		example: "To differentiate the function 2<i>x</i> + 3/(<i>x</i><sup>4</sup> + 5) at <i>x</i> = 1, type <tt>/1/2x+3d(x**4+5)</tt> after the current url address. The results for the values of the function and of its first three derivatives should be <tt>2.5, 1.66..., -0.55..., and 1.11...</tt>  Typing <tt>/1/2x+3d(x**4+5)?order=5</tt> instead will also give the fourth and fifth derivatives: <tt>7.03... and -16.79...</tt>  Typing <tt>/1/2x+3d(x**4+5)?method=complex</tt> will give the function and its first derivative, accurate to more digits.  Typing <tt>/0/abs(x)</tt> will report that the derivative is <tt>-1</tt> from the left but <tt>1</tt> from the right.  Typing <tt>/table/0/2/10/2x+3d(x**4+5)</tt> will tabulate the function and its first two derivatives at <i>x</i> = 0, 0.2, 0.4, ... 2.".to_string(),
+		algorithm: "finite differences (with weights from Fornberg's algorithm) for sequences of decreasing values of &Delta;<i>x</i> (starting from several values scaled to the magnitude of <i>x</i>), excluding any reference to the particular point itself in the case of a removable singularity, followed by Richardson extrapolation to &Delta;<i>x</i> = 0 (Ridders' method), or else the complex-step method: <i>f'</i>(<i>x</i>) &asymp; Im <i>f</i>(<i>x</i> + <i>ih</i>)/<i>h</i> for <i>h</i> = 10<sup>-20</sup>, which involves no subtraction and therefore no cancellation.  One-sided values come from stencils on one side of the point, which have an additional node so that their leading errors are of order &Delta;<i>x</i><sup>2</sup>.  The one-sided value of the function is <i>f</i>(<i>x</i>) itself if its differences from nearby values shrink steadily as &Delta;<i>x</i> shrinks.  A one-sided derivative is considered infinite if its extrapolations move steadily away from 0 by more than their estimated errors, which do not shrink, as the starting &Delta;<i>x</i> shrinks.  Only the function and its first derivative are compared to detect a jump, corner, or cusp, beyond which no central derivative is calculated".to_string(),
+		json: "Type '/json' in the url bar immediately after 'differentiation' if you would like the result in this format rather than html.  A successful response will contain ten properties: 'x' (a float), 'method' (either 'finite' or 'complex'), 'nonsingular' (a boolean reflecting whether or not the function has a removable singularity), 'derivs' (an array of floats whose values represent the function value and its derivatives of successively higher order, by default through the third), 'errors' (an array of floats whose values are the estimated absolute errors of the elements of 'derivs'), 'left' and 'right' (arrays like 'derivs' but calculated from only one side of the point, or null if the function cannot be evaluated on that side or if the method is 'complex'), 'left_errors' and 'right_errors' (arrays of the estimated absolute errors of the elements of 'left' and 'right', or null along with them), and 'corner' (a boolean reflecting whether or not the left and right values of the function or of its first derivative disagree).  When 'corner' is true, the elements of 'derivs' and 'errors' are null from the first order at which the two sides disagree, since no derivative exists there.  A derivative which diverges on one side of the point is infinite (null in json), with an infinite error.  For a table, type '/json' or '/csv' immediately after 'table'.  A successful json response will contain four properties: 'xmin', 'xmax', 'n' (the number of intervals), and 'rows' (an array of n + 1 objects, each with the ten properties described above).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
 	}
 }
 
@@ -20,42 +20,495 @@ pub fn page() -> String {helper::format(instructions())}
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Results {
 	pub x: f64,
+	pub method: String,
 	pub nonsingular: bool,
 	pub derivs: Vec<f64>,
+	pub errors: Vec<f64>,
+	pub left: Option<Vec<f64>>,
+	pub right: Option<Vec<f64>>,
+	pub left_errors: Option<Vec<f64>>,
+	pub right_errors: Option<Vec<f64>>,
+	pub corner: bool,
 }
 
-pub fn raw (x_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+// parameters for Ridders' version of Richardson extrapolation (See Numerical Recipes.)
+const CON: f64 = 1.4; // factor by which the step shrinks between successive estimates
+const NTAB: usize = 10; // maximum number of estimates
+const SAFE: f64 = 2.; // quit when the error has grown by this factor
+const H_MIN: f64 = 0.0001; // smallest starting step to try
+pub const MAX_ORDER: i32 = 10; // highest derivative which may be requested
+const MAX_INTERVALS: i32 = 1000; // largest number of intervals in a table
+const H_COMPLEX: f64 = 1e-20; // imaginary step for the complex-step method
+
+// Largest starting step, which is scaled to the magnitude of x.  Higher derivatives require
+// bigger steps, because their stencils amplify roundoff more.
+pub fn step(x: f64, order: i32) -> f64 {
+	0.1 * x.abs().max(1.) * (order.max(1) as f64)
+}
+
+// A finite-difference stencil is a list of (offset, weight) pairs, for which the offsets are
+// in units of the step h and the weighted sum of function values must be divided by h^order.
+pub struct Stencil {
+	pub order: i32,
+	pub points: Vec<(f64, f64)>,
+}
+
+// Applies a stencil at x with step h, returning the estimate and the size of its roundoff error.
+pub fn apply(function: &dyn Fn(f64) -> Result<f64, String>, x: f64, stencil: &Stencil, h: f64) -> Result<(f64, f64), String> {
+	let mut sum = 0.;
+	let mut noise = 0.;
+	for (offset, weight) in &stencil.points {
+		let f = match function(x + offset * h) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		};
+		sum += weight * f;
+		noise += (weight * f).abs();
+	}
+	let scale = h.powi(stencil.order);
+	Ok((sum / scale, f64::EPSILON * noise / scale))
+}
+
+// Extrapolates to zero step size a finite-difference estimate whose error is a series in
+// h^power, returning the extrapolated value and an estimate of its error.
+pub fn extrapolate(stencil: &dyn Fn(f64) -> Result<(f64, f64), String>, h0: f64, power: i32) -> Result<(f64, f64), String> {
+	let con_power = CON.powi(power);
+	let mut h = h0;
+	// Each row of this Neville tableau is one order of extrapolation higher than the previous.
+	let mut table: Vec<Vec<f64>> = vec![];
+	let mut value = f64::NAN;
+	let mut error = f64::INFINITY;
+	// Roundoff is worst for the smallest step, which is the last one used.
+	let mut noise = 0.;
+	for i in 0..NTAB {
+		let estimate = match stencil(h) {
+			Ok((estimate, roundoff)) => {
+				noise = roundoff;
+				estimate
+			},
+			Err(message) => {
+				// A large step may leave the function's domain, so shrink it and try again.
+				if table.is_empty() && i < NTAB - 1 {
+					h /= CON;
+					continue;
+				}
+				if table.is_empty() {
+					return Err(message);
+				}
+				break;
+			},
+		};
+		h /= CON;
+		if table.is_empty() {
+			table.push(vec![estimate]);
+			value = estimate;
+			continue;
+		}
+		let n = table.len();
+		let mut row = vec![estimate];
+		let mut fac = con_power;
+		for j in 1..=n {
+			row.push((row[j - 1] * fac - table[n - 1][j - 1]) / (fac - 1.));
+			fac *= con_power;
+			let err = (row[j] - row[j - 1]).abs().max((row[j] - table[n - 1][j - 1]).abs());
+			if err <= error {
+				error = err;
+				value = row[j];
+			}
+		}
+		// Higher-order extrapolation is no longer helping, because of roundoff.
+		let done = (row[n] - table[n - 1][n - 1]).abs() >= SAFE * error;
+		table.push(row);
+		if done {
+			break;
+		}
+	}
+	Ok((value, error.max(noise)))
+}
+
+// extrapolations of the estimate (a function of step size) from the starting steps h_max,
+// h_max/10, ..., none of which is smaller than H_MIN
+pub fn extrapolations(estimate: &dyn Fn(f64) -> Result<(f64, f64), String>, h_max: f64, power: i32) -> Vec<Result<(f64, f64), String>> {
+	let mut results = vec![];
+	let mut h = h_max;
+	while h >= H_MIN {
+		results.push(extrapolate(estimate, h, power));
+		h /= 10.;
+	}
+	results
+}
+
+// The best step depends upon how rapidly the function varies, which is unknown beforehand.
+// Extrapolation therefore starts from a range of different steps.  Small steps suffer from
+// roundoff, and big ones may be fooled by oscillations, so the least uncertain result wins.
+// Uncertainty is measured by relative error, except for results which are consistent with 0.
+// This returns the index of the winner, or the last error if no extrapolation succeeded.
+pub fn least_uncertain(results: &[Result<(f64, f64), String>]) -> Result<usize, String> {
+	let uncertainty = |value: f64, error: f64| {
+		(if error == 0. {0.} else {(error / value.abs()).min(1.)}, error)
+	};
+	let mut best: Result<usize, String> = Err("Error: no step size was attempted.".to_string());
+	for (i, result) in results.iter().enumerate() {
+		match result {
+			Ok((value, error)) => {
+				let better = match best {
+					Ok(j) => match results[j] {
+						Ok((best_value, best_error)) => uncertainty(*value, *error) < uncertainty(best_value, best_error),
+						Err(_) => true,
+					},
+					Err(_) => true,
+				};
+				if better {
+					best = Ok(i);
+				}
+			},
+			Err(message) => if best.is_err() {
+				best = Err(message.clone());
+			},
+		}
+	}
+	best
+}
+
+// the least uncertain extrapolation from steps no larger than h_max
+pub fn differentiate(estimate: &dyn Fn(f64) -> Result<(f64, f64), String>, h_max: f64, power: i32) -> Result<(f64, f64), String> {
+	let results = extrapolations(estimate, h_max, power);
+	match least_uncertain(&results) {
+		Ok(i) => results[i].clone(),
+		Err(message) => Err(message),
+	}
+}
+
+// Fornberg's algorithm for the weights of the derivative of a given order at x = 0, based
+// upon the function's values at the given nodes (whose units are the step h)
+pub fn stencil(order: i32, nodes: Vec<f64>) -> Stencil {
+	let m = order as usize;
+	let n = nodes.len();
+	// c[k][j] is the weight of node j for the k-th derivative.
+	let mut c = vec![vec![0.; n]; m + 1];
+	c[0][0] = 1.;
+	let mut c1 = 1.;
+	let mut c4 = nodes[0];
+	for i in 1..n {
+		let mn = i.min(m);
+		let mut c2 = 1.;
+		let c5 = c4;
+		c4 = nodes[i];
+		for j in 0..i {
+			let c3 = nodes[i] - nodes[j];
+			c2 *= c3;
+			if j == i - 1 {
+				for k in (1..=mn).rev() {
+					c[k][i] = c1 * (k as f64 * c[k - 1][i - 1] - c5 * c[k][i - 1]) / c2;
+				}
+				c[0][i] = -c1 * c5 * c[0][i - 1] / c2;
+			}
+			for k in (1..=mn).rev() {
+				c[k][j] = (c4 * c[k][j] - k as f64 * c[k - 1][j]) / c3;
+			}
+			c[0][j] = c4 * c[0][j] / c3;
+		}
+		c1 = c2;
+	}
+	Stencil {order, points: nodes.into_iter().zip(c[m].iter().cloned()).collect()}
+}
+
+// Symmetric stencils have errors which are series in h^2.  The point itself is needed only
+// for derivatives of even order, and - in the case of a removable singularity - never.
+pub fn central(order: i32, nonsingular: bool) -> Stencil {
+	let mut nodes = vec![];
+	if nonsingular && order % 2 == 0 {
+		nodes.push(0.);
+	}
+	// enough nodes to determine a polynomial of degree order
+	while nodes.len() < order as usize + 1 {
+		let m = (nodes.len() / 2 + 1) as f64;
+		nodes.append(&mut vec![m, -m]);
+	}
+	stencil(order, nodes)
+}
+
+// Stencils on one side of the point (direction = 1 for the right and -1 for the left) have
+// errors which are series in h, so an extra node is used to make the leading error of order h^2.
+// The point itself is excluded for the function value, which is then the one-sided limit.
+pub fn one_sided(order: i32, nonsingular: bool, direction: f64) -> Stencil {
+	let first = if nonsingular && order > 0 {0} else {1};
+	let nodes = (first..first + order + 2).map(|m| direction * m as f64).collect();
+	stencil(order, nodes)
+}
+
+pub fn raw (x_str: &RawStr, input_str: &RawStr, order_str: Option<&RawStr>, method_str: Option<&RawStr>) -> Result<Results, String> {
 	let x = match helper::parse_expression(x_str.to_string()) {
 	  Ok(x) => x,
 	  Err(message) => return Err(message),
 	};
-	let f = helper::function1(input_str.to_string(), x);
-	let dx = 0.001;
-	let steps = vec![2., 1., -1., -2.];
-	let mut fs = vec![];
-	for step in steps {
-	  fs.push(match helper::function1(input_str.to_string(), x + step * dx) {
+	let complex = match method_str {
+		None => false,
+		Some(method_str) => match method_str.to_lowercase().as_str() {
+			"finite" => false,
+			"complex" => true,
+			_ => return Err(format!("{} is not a method.  Use either finite or complex.", method_str)),
+		},
+	};
+	let order = match order_str {
+		None => if complex {1} else {3},
+		Some(order_str) => match helper::parse_expression(order_str.to_string()) {
+			Ok(order) => {
+				if order.round() != order {
+					return Err(format!("{} is not an integer.", order));
+				} else if order < 0. || order > MAX_ORDER as f64 {
+					return Err(format!("The order must be between 0 and {}.", MAX_ORDER));
+				}
+				order as i32
+			},
+			Err(message) => return Err(message),
+		},
+	};
+	if complex {
+		if order > 1 {
+			return Err("The complex-step method calculates only the first derivative.".to_string());
+		}
+		return complex_step(&|z| helper::complex_function1(input_str.to_string(), z), x, order);
+	}
+	let function = |x| helper::function1(input_str.to_string(), x);
+	derivatives(&function, x, order)
+}
+
+// The imaginary part of f(x + ih) is hf'(x), to an accuracy of order h^3.
+pub fn complex_step(function: &dyn Fn(helper::Complex) -> Result<helper::Complex, String>, x: f64, order: i32) -> Result<Results, String> {
+	let f = match function(helper::Complex {re: x, im: H_COMPLEX}) {
 		Ok(f) => f,
 		Err(message) => return Err(message),
-	  });
+	};
+	let mut derivs = vec![f.re];
+	let mut errors = vec![0.];
+	if order > 0 {
+		let deriv = f.im / H_COMPLEX;
+		derivs.push(deriv);
+		errors.push(f64::EPSILON * deriv.abs());
 	}
-	let mut f0 = 0.;
+	Ok(Results {
+		x,
+		method: "complex".to_string(),
+		nonsingular: true,
+		derivs,
+		errors,
+		left: None,
+		right: None,
+		left_errors: None,
+		right_errors: None,
+		corner: false,
+	})
+}
+
+// One-sided extrapolations diverge if, as the starting step shrinks, they keep moving away from
+// 0 by more than their estimated errors while those errors do not shrink.  (Convergent ones settle
+// down instead, and ones spoilt by roundoff wander in sign.)
+fn diverges(results: &[Result<(f64, f64), String>]) -> bool {
+	let estimates: Vec<(f64, f64)> = results.iter().filter_map(|result| result.clone().ok()).collect();
+	if estimates.len() < 3 {
+		return false;
+	}
+	estimates[estimates.len() - 3..].windows(2).all(|pair| {
+		let ((value0, error0), (value1, error1)) = (pair[0], pair[1]);
+		value0 * value1 > 0. && value1.abs() - value0.abs() > error0 + error1 && error1 >= error0 / 2.
+	})
+}
+
+// For a function which exists at x, the one-sided limit is f(x) itself if the differences from it
+// shrink steadily as x is approached.  This is surer than extrapolation when the function's series
+// is in a fractional power of the distance from x (as at the edge of a square root's domain).
+fn continuous(function: &dyn Fn(f64) -> Result<f64, String>, x: f64, direction: f64) -> Result<Option<(f64, f64)>, String> {
+	let f = match function(x) {
+		Ok(f) => f,
+		Err(message) => return Err(message),
+	};
+	let mut differences = vec![];
+	for h in &[1e-4, 1e-8, 1e-12] {
+		match function(x + direction * h * x.abs().max(1.)) {
+			Ok(value) => differences.push((value - f).abs()),
+			Err(message) => return Err(message),
+		}
+	}
+	let shrinking = differences[1] <= differences[0] && differences[2] <= differences[1] && differences[2] <= 0.01 * differences[0];
+	Ok(if shrinking {Some((f, differences[2]))} else {None})
+}
+
+// one-sided values and errors of the function and its derivatives (infinite for any which diverge),
+// or None if the function cannot be evaluated on that side
+fn side(function: &dyn Fn(f64) -> Result<f64, String>, x: f64, order: i32, nonsingular: bool, direction: f64) -> Option<(Vec<f64>, Vec<f64>)> {
+	let mut derivs = vec![];
+	let mut errors = vec![];
+	for order in 0..=order {
+		if order == 0 && nonsingular {
+			match continuous(function, x, direction) {
+				Ok(Some((value, error))) => {
+					derivs.push(value);
+					errors.push(error);
+					continue;
+				},
+				Ok(None) => (),
+				Err(_) => return None,
+			}
+		}
+		let stencil = one_sided(order, nonsingular, direction);
+		let results = extrapolations(&|h| apply(function, x, &stencil, h), step(x, order), 1);
+		if diverges(&results) {
+			let last = results.iter().rev().find_map(|result| result.clone().ok());
+			derivs.push(f64::INFINITY * last.map_or(1., |(value, _)| value.signum()));
+			errors.push(f64::INFINITY);
+			continue;
+		}
+		match least_uncertain(&results) {
+			// A function may be NaN rather than an error outside its domain (eg, a fractional power of a negative number).
+			Ok(i) => match results[i] {
+				Ok((deriv, _)) if deriv.is_nan() => return None,
+				Ok((deriv, error)) => {
+					derivs.push(deriv);
+					errors.push(error);
+				},
+				Err(_) => return None,
+			},
+			Err(_) => return None,
+		}
+	}
+	Some((derivs, errors))
+}
+
+// Values from the two sides disagree if they differ by much more than their estimated errors, or
+// if either diverges and they are not equal.
+pub fn disagree(left: (f64, f64), right: (f64, f64)) -> bool {
+	if left.0.is_infinite() || right.0.is_infinite() {
+		return left.0 != right.0;
+	}
+	let scale = left.0.abs().max(right.0.abs()).max(1.);
+	(left.0 - right.0).abs() > 3. * (left.1 + right.1) + 1e-6 * scale
+}
+
+// the function and its derivatives through the given order, at one point
+pub fn derivatives(function: &dyn Fn(f64) -> Result<f64, String>, x: f64, order: i32) -> Result<Results, String> {
+	let f = function(x);
 	// I prob need to implement better testing for this.
 	let nonsingular = f.is_ok();
-	if nonsingular {
-	  f0 = f.unwrap();
-	}
-	let derivs = vec![
-	  // How to use values at discrete points to calculate function and derivative values
-	  // For every other case, allowance needs to be made for a removable singularity.
-	  if nonsingular {f0} else {(fs[1] + fs[2]) / 2.},
-	  (fs[1] - fs[2]) / 2. / dx,
-	  if nonsingular {(fs[1] - 2. * f0 + fs[2]) / dx / dx} else {(fs[0] - fs[1] - fs[2] + fs[3]) / 3. / dx / dx},
-	  (fs[0] - fs[3] - 2. * fs[1] + 2. * fs[2]) / 2. / dx / dx / dx,
-	];
+	let left = side(function, x, order, nonsingular, -1.);
+	let right = side(function, x, order, nonsingular, 1.);
+	let mut derivs = vec![];
+	let mut errors = vec![];
+	let mut corner = false;
+	for order in 0..=order {
+		let k = order as usize;
+		// One-sided derivatives of higher order are too inaccurate to compare.
+		if let (Some(left), Some(right), true) = (&left, &right, order <= 1) {
+			corner = corner || disagree((left.0[k], left.1[k]), (right.0[k], right.1[k]));
+		}
+		let (deriv, error) = match (order, &f) {
+			(0, Ok(f0)) => (*f0, 0.),
+			// No derivative exists beyond a jump or corner, and a central difference across it is meaningless.
+			_ if corner => (f64::NAN, f64::NAN),
+			_ => match differentiate(&|h| apply(function, x, &central(order, nonsingular), h), step(x, order), 2) {
+				Ok(result) => result,
+				// At a boundary of the function's domain only one side may be available.
+				Err(message) => match (&left, &right) {
+					(Some((derivs, errors)), None) | (None, Some((derivs, errors))) => (derivs[k], errors[k]),
+					_ => return Err(message),
+				},
+			},
+		};
+		derivs.push(deriv);
+		errors.push(error);
+	}
+	let (left, left_errors) = match left {
+		Some((derivs, errors)) => (Some(derivs), Some(errors)),
+		None => (None, None),
+	};
+	let (right, right_errors) = match right {
+		Some((derivs, errors)) => (Some(derivs), Some(errors)),
+		None => (None, None),
+	};
 	Ok(Results {
-		x: x,
-		nonsingular: nonsingular,
-		derivs: derivs,
+		x,
+		method: "finite".to_string(),
+		nonsingular,
+		derivs,
+		errors,
+		left,
+		right,
+		left_errors,
+		right_errors,
+		corner,
 	})
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Table {
+	pub xmin: f64,
+	pub xmax: f64,
+	pub n: i32,
+	pub rows: Vec<Results>,
+}
+
+pub fn table(xmin_str: &RawStr, xmax_str: &RawStr, n_str: &RawStr, input_str: &RawStr) -> Result<Table, String> {
+	let xmin = match helper::parse_expression(xmin_str.to_string()) {
+		Ok(xmin) => xmin,
+		Err(message) => return Err(message),
+	};
+	let xmax = match helper::parse_expression(xmax_str.to_string()) {
+		Ok(xmax) => xmax,
+		Err(message) => return Err(message),
+	};
+	let n = match helper::parse_expression(n_str.to_string()) {
+		Ok(n) => {
+			if n.round() != n {
+				return Err(format!("{} is not an integer.", n));
+			} else if n <= 0. || n > MAX_INTERVALS as f64 {
+				return Err(format!("The number of intervals must be between 1 and {}.", MAX_INTERVALS));
+			}
+			n as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let mut rows = vec![];
+	for i in 0..=n {
+		let x = xmin + (i as f64) * (xmax - xmin) / (n as f64);
+		rows.push(match derivatives(&function, x, 2) {
+			Ok(results) => results,
+			Err(message) => return Err(format!("Error at x = {}: {}", x, message)),
+		});
+	}
+	Ok(Table {xmin, xmax, n, rows})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn smooth_at_large_x() {
+		// The extrapolations wander with roundoff here but do not grow, so neither side diverges.
+		let results = raw(RawStr::from_str("1000000"), RawStr::from_str("sin(x)"), None, None).unwrap();
+		assert!(!results.corner);
+		assert!((results.derivs[1] - 1e6_f64.cos()).abs() < 1e-6);
+	}
+
+	#[test]
+	fn corner_of_abs() {
+		let results = raw(RawStr::from_str("0"), RawStr::from_str("abs(x)"), None, None).unwrap();
+		assert!(results.corner);
+		assert_eq!(results.derivs[0], 0.);
+		assert!(results.derivs[1].is_nan() && results.derivs[2].is_nan());
+		assert!((results.left.unwrap()[1] + 1.).abs() < 1e-6);
+		assert!((results.right.unwrap()[1] - 1.).abs() < 1e-6);
+	}
+
+	#[test]
+	fn edge_of_domain() {
+		let results = raw(RawStr::from_str("0"), RawStr::from_str("sqrt(x)"), None, None).unwrap();
+		assert!(results.left.is_none());
+		assert_eq!(results.right.unwrap()[0], 0.);
+		assert_eq!(results.derivs[1], f64::INFINITY);
+		let results = raw(RawStr::from_str("0"), RawStr::from_str("x**(1d3)"), None, None).unwrap();
+		assert!(!results.corner);
+		assert_eq!(results.right.unwrap()[0], 0.);
+	}
+}
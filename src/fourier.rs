@@ -0,0 +1,179 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integration::{self, Settings};
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "FOURIER SERIES".to_string(),
+		links: calculus::links(17),
+		instructions: "This page calculates the Fourier series of a periodic function, ie the coefficients <i>a<sub>n</sub></i> and <i>b<sub>n</sub></i> for which <i>f</i>(<i>x</i>) &asymp; <i>a</i><sub>0</sub>/2 + &Sigma;[<i>a<sub>n</sub></i>cos(2&pi;<i>nx</i>/<i>P</i>) + <i>b<sub>n</sub></i>sin(2&pi;<i>nx</i>/<i>P</i>)], in which <i>P</i> is the period and the sum runs from <i>n</i> = 1 to <i>N</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/fourier</tt> type the following:<p align=center><tt>&sol;&lt;period&gt;&sol;&lt;number of terms <i>N</i> (no greater than 100)&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>The function need only be defined over one period, which by default extends from -<i>P</i>/2 to <i>P</i>/2.  To instead start the period at some other value of <i>x</i>, append <tt>?start=&lt;value&gt;</tt> to the url.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the period and the start)", helper::NOTE2),
+		example: "To calculate the first 5 terms of the series for a square wave of period 2, type <tt>/2/5/signum(x)</tt> after the current url address.  The coefficients <i>b</i><sub>1</sub>, <i>b</i><sub>3</sub>, and <i>b</i><sub>5</sub> should be <tt>1.273...</tt>, <tt>0.424...</tt>, and <tt>0.254...</tt> (ie, 4/(<i>n</i>&pi;)), and all of the others should vanish.".to_string(),
+		algorithm: "<i>a<sub>n</sub></i> and <i>b<sub>n</sub></i> are 2/<i>P</i> times the integrals over one period of <i>f</i>(<i>x</i>)cos(2&pi;<i>nx</i>/<i>P</i>) and <i>f</i>(<i>x</i>)sin(2&pi;<i>nx</i>/<i>P</i>), each of which is calculated by the 21-point Gauss-Kronrod rule of the integration page.  The error of the truncation is the square root of the integral over one period of the square of the difference between the function and the partial sum, which is calculated in the same way".to_string(),
+		json: "Type '/json' in the url bar immediately after 'fourier' if you would like the result in this format rather than html.  A successful response will contain seven properties: 'period' and 'start' (floats), 'terms' (an array of objects, one for each value of <i>n</i> from 0 to <i>N</i>, with properties 'n', 'a', 'b', and 'error' (the larger of the estimated absolute errors of <i>a<sub>n</sub></i> and <i>b<sub>n</sub></i>)), 'series' (the partial sum, as a string which uses this app's syntax for functions and which omits any term whose coefficient is smaller than its estimated error), 'l2_error' (the error of the truncation), 'l2_norm' (the square root of the integral of the square of the function over one period, for comparison), and 'converged' (false if any of the integrals reached the cap on its number of subdivisions before converging).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Term {
+	pub n: i32,
+	pub a: f64,
+	pub b: f64,
+	pub error: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub period: f64,
+	pub start: f64,
+	pub terms: Vec<Term>,
+	pub series: String,
+	pub l2_error: f64,
+	pub l2_norm: f64,
+	pub converged: bool,
+}
+
+const MAX_TERMS: i32 = 100;
+const TOLERANCE: f64 = 1e-10; // relative accuracy of each integral
+
+fn settings() -> Settings {
+	Settings {rel_epsilon: TOLERANCE, method: "gk21".to_string(), ..Settings::default()}
+}
+
+// the partial sum, written so that it may be pasted into the url of any page of this app
+fn series(terms: &[Term], omega: f64, errors: &[(f64, f64)]) -> String {
+	let mut text = "".to_string();
+	let mut append = |coefficient: f64, error: f64, factor: String| {
+		if coefficient.abs() <= error {
+			return;
+		}
+		let sign = if coefficient < 0. {"-"} else if text.is_empty() {""} else {"+"};
+		text = format!("{}{}{}{}", text, sign, coefficient.abs(), factor);
+	};
+	for (term, (a_error, b_error)) in terms.iter().zip(errors) {
+		if term.n == 0 {
+			append(term.a / 2., a_error / 2., "".to_string());
+			continue;
+		}
+		let k = term.n as f64 * omega;
+		append(term.a, *a_error, format!("*cos({}*x)", k));
+		append(term.b, *b_error, format!("*sin({}*x)", k));
+	}
+	if text.is_empty() {"0".to_string()} else {text}
+}
+
+pub fn raw(period_str: &RawStr, nterms_str: &RawStr, input_str: &RawStr, start_str: Option<&RawStr>) -> Result<Results, String> {
+	let period = match helper::parse_expression(period_str.to_string()) {
+		Ok(period) => {
+			if !period.is_finite() || period <= 0. {
+				return Err(format!("The period ({}) must be positive and finite.", period));
+			}
+			period
+		},
+		Err(message) => return Err(message),
+	};
+	let nterms = match helper::parse_expression(nterms_str.to_string()) {
+		Ok(nterms) => {
+			if nterms.round() != nterms {
+				return Err(format!("{} is not an integer.", nterms));
+			} else if nterms < 0. || nterms > MAX_TERMS as f64 {
+				return Err(format!("The number of terms must be between 0 and {}.", MAX_TERMS));
+			}
+			nterms as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let start = match start_str {
+		None => -period / 2.,
+		Some(start_str) => match helper::parse_expression(start_str.to_string()) {
+			Ok(start) => start,
+			Err(message) => return Err(message),
+		},
+	};
+	let (xi, xf) = (start, start + period);
+	let omega = 2. * std::f64::consts::PI / period;
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let settings = settings();
+	let mut converged = true;
+	let mut terms = vec![];
+	let mut errors = vec![];
+	for n in 0..=nterms {
+		let k = n as f64 * omega;
+		let mut coefficients = vec![];
+		for (j, trig) in [f64::cos, f64::sin].iter().enumerate() {
+			// b0 vanishes identically.
+			if n == 0 && j == 1 {
+				coefficients.push((0., 0.));
+				continue;
+			}
+			let integrand = |x: f64| -> Result<f64, String> {
+				match function(x) {
+					Ok(f) => Ok(f * trig(k * x)),
+					Err(message) => Err(message),
+				}
+			};
+			coefficients.push(match integration::integrate(&integrand, xi, xf, &settings) {
+				Ok(estimate) => {
+					converged = converged && estimate.converged;
+					(2. * estimate.integral / period, 2. * estimate.error / period)
+				},
+				Err(message) => return Err(message),
+			});
+		}
+		let ((a, a_error), (b, b_error)) = (coefficients[0], coefficients[1]);
+		terms.push(Term {n, a, b, error: a_error.max(b_error)});
+		errors.push((a_error, b_error));
+	}
+	// The partial sum is evaluated directly, rather than by parsing the series.
+	let sum = |x: f64| terms.iter().fold(0., |sum, term| {
+		let k = term.n as f64 * omega;
+		let a = if term.n == 0 {term.a / 2.} else {term.a};
+		sum + a * (k * x).cos() + term.b * (k * x).sin()
+	});
+	let mut squares = vec![];
+	for difference in [true, false] {
+		let integrand = |x: f64| -> Result<f64, String> {
+			match function(x) {
+				Ok(f) => Ok(if difference {(f - sum(x)).powi(2)} else {f * f}),
+				Err(message) => Err(message),
+			}
+		};
+		squares.push(match integration::integrate(&integrand, xi, xf, &settings) {
+			Ok(estimate) => {
+				converged = converged && estimate.converged;
+				estimate.integral.max(0.)
+			},
+			Err(message) => return Err(message),
+		});
+	}
+	Ok(Results {
+		period,
+		start,
+		series: series(&terms, omega, &errors),
+		terms,
+		l2_error: squares[0].sqrt(),
+		l2_norm: squares[1].sqrt(),
+		converged,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn square_wave_example() {
+		let results = raw(RawStr::from_str("2"), RawStr::from_str("5"), RawStr::from_str("signum(x)"), None).unwrap();
+		let pi = std::f64::consts::PI;
+		for term in &results.terms {
+			let b = if term.n % 2 == 1 {4. / (term.n as f64 * pi)} else {0.};
+			assert!(term.a.abs() < 1e-8);
+			assert!((term.b - b).abs() < 1e-8);
+		}
+		assert!(results.converged);
+	}
+}
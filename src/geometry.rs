@@ -0,0 +1,245 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::differentiation;
+use crate::integration::{self, Settings};
+use crate::root_finding;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "GEOMETRY".to_string(),
+		links: calculus::links(14),
+		instructions: "This page applies integration to the curve <i>y</i> = <i>f</i>(<i>x</i>) between two values of <i>x</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/geometry</tt> type one of the following:<ul><li>for the length of the curve: <tt>&sol;arc-length&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;function of <i>x</I>&gt;</tt></li><li>for the area between two curves <i>y</i> = <i>f</i>(<i>x</i>) and <i>y</i> = <i>g</i>(<i>x</i>), with their intersections between the limits found automatically: <tt>&sol;area&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;function <i>f</i>&gt;&sol;&lt;function <i>g</i>&gt;</tt></li><li>for the volume and (curved) surface area of the solid formed by revolving the region between the curve and the <i>x</i>-axis about either the <i>x</i>-axis or the <i>y</i>-axis: <tt>&sol;revolution&sol;&lt;x or y&gt;&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;function of <i>x</I>&gt;</tt></li></ul>The limits must be finite, and for a revolution about the <i>y</i>-axis they must not lie on opposite sides of it (since the parts of the region on either side would sweep out overlapping solids).  Each result is accompanied by the integral which was set up to calculate it.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the limits)", helper::NOTE2),
+		example: "Typing <tt>/arc-length/0/1/x**2</tt> after the current url address should give <tt>1.4789...</tt>  Typing <tt>/area/0/2/x**2/x</tt> should find the intersection at <i>x</i> = 1 and give an area of <tt>1</tt>.  Typing <tt>/revolution/x/0/1/sqrt(1-x**2)</tt> should give the volume and surface area of a hemisphere: <tt>2.094...</tt> (ie, 2&pi;/3) and <tt>6.283...</tt> (ie, 2&pi;).".to_string(),
+		algorithm: "the integrals are calculated by the 21-point Gauss-Kronrod rule of the integration page (or by the tanh-sinh rule, if the integrand cannot be evaluated at an endpoint), with the derivative <i>f</i>'(<i>x</i>) calculated as on the differentiation page.  Intersections are found by looking for changes in the sign of <i>f</i> - <i>g</i> at 100 equally spaced points, each of which is then refined as on the root-finding page.  (Intersections at which the curves touch without crossing are therefore not found, but they do not affect the area.)  Volumes about the <i>x</i>-axis are calculated by disks, and those about the <i>y</i>-axis by cylindrical shells".to_string(),
+		json: "Type '/json' in the url bar immediately after 'arc-length', 'area', or 'revolution' if you would like the result in this format rather than html.  A successful response will contain five properties: 'xi' and 'xf' (the limits), 'axis' (a string, or null except for a solid of revolution), 'intersections' (an array of floats, which is empty except for an area), and 'quantities' (an array of objects, each with properties 'name' (eg, 'volume'), 'setup' (a string representing the integral), 'value', 'error' (the estimated absolute error of the value), and 'converged' (as on the integration page)).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Quantity {
+	pub name: String,
+	pub setup: String,
+	pub value: f64,
+	pub error: f64,
+	pub converged: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub xi: f64,
+	pub xf: f64,
+	pub axis: Option<String>,
+	pub intersections: Vec<f64>,
+	pub quantities: Vec<Quantity>,
+}
+
+const SAMPLES: i32 = 100; // number of intervals in which to look for an intersection
+const TOLERANCE: f64 = 1e-10; // relative accuracy, which is limited by that of the derivatives
+
+// The derivatives in some integrands limit their accuracy to less than the default.
+fn settings() -> Settings {
+	Settings {rel_epsilon: TOLERANCE, method: "gk21".to_string(), ..Settings::default()}
+}
+
+// the expression as it would be typed, apart from url encoding
+fn display(input_str: &RawStr) -> String {
+	let mut expression = input_str.to_string();
+	expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", "");
+	for stri in ["div", "DIV", "d", "D"] {
+		expression = str::replace(&expression, stri, "/");
+	}
+	expression
+}
+
+fn limits(xi_str: &RawStr, xf_str: &RawStr) -> Result<(f64, f64), String> {
+	let mut limits = vec![];
+	for limit_str in &[xi_str, xf_str] {
+		limits.push(match helper::parse_expression(limit_str.to_string()) {
+			Ok(limit) => limit,
+			Err(message) => return Err(message),
+		});
+	}
+	if limits[0] == limits[1] {
+		return Err("The limits must differ.".to_string());
+	}
+	Ok((limits[0], limits[1]))
+}
+
+// the derivative f'(x)
+fn slope(function: &dyn Fn(f64) -> Result<f64, String>, x: f64) -> Result<f64, String> {
+	let stencil = differentiation::central(1, true);
+	match differentiation::differentiate(&|h| differentiation::apply(function, x, &stencil, h), differentiation::step(x, 1), 2) {
+		Ok((slope, _)) => Ok(slope),
+		Err(message) => Err(format!("Cannot differentiate function at x = {}: {}", x, message)),
+	}
+}
+
+// sqrt(1 + f'(x)^2), which is the length of the curve per unit of x
+fn stretch(function: &dyn Fn(f64) -> Result<f64, String>, x: f64) -> Result<f64, String> {
+	match slope(function, x) {
+		Ok(slope) => Ok((1. + slope * slope).sqrt()),
+		Err(message) => Err(message),
+	}
+}
+
+fn quantity(name: &str, setup: String, factor: f64, integrand: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64) -> Result<Quantity, String> {
+	match integration::integrate(integrand, xi, xf, &settings()) {
+		Ok(estimate) => Ok(Quantity {
+			name: name.to_string(),
+			setup,
+			// Each quantity is positive, regardless of the order of the limits.
+			value: (factor * estimate.integral).abs(),
+			error: factor * estimate.error,
+			converged: estimate.converged,
+		}),
+		Err(message) => Err(message),
+	}
+}
+
+pub fn arc_length(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let (xi, xf) = match limits(xi_str, xf_str) {
+		Ok(limits) => limits,
+		Err(message) => return Err(message),
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let setup = format!("integral from {} to {} of sqrt(1 + (f'(x))^2) dx, in which f(x) = {}", xi, xf, display(input_str));
+	let length = match quantity("arc length", setup, 1., &|x| stretch(&function, x), xi, xf) {
+		Ok(length) => length,
+		Err(message) => return Err(message),
+	};
+	Ok(Results {xi, xf, axis: None, intersections: vec![], quantities: vec![length]})
+}
+
+// the values of x between the limits at which the function changes sign
+fn roots(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64) -> Result<Vec<f64>, String> {
+	let mut roots = vec![];
+	let mut x0 = xi;
+	let mut f0 = match function(x0) {
+		Ok(f0) => f0,
+		Err(message) => return Err(message),
+	};
+	for i in 1..=SAMPLES {
+		let x2 = xi + (i as f64) * (xf - xi) / (SAMPLES as f64);
+		let f2 = match function(x2) {
+			Ok(f2) => f2,
+			Err(message) => return Err(message),
+		};
+		if f2 == 0. {
+			if i < SAMPLES {
+				roots.push(x2);
+			}
+		} else if f0 * f2 < 0. {
+			let (a, b, fa, fb) = if x0 < x2 {(x0, x2, f0, f2)} else {(x2, x0, f2, f0)};
+			roots.push(match root_finding::refine(function, a, b, fa, fb, integration::EPSILON) {
+				Ok((root, _)) => root,
+				Err(message) => return Err(message),
+			});
+		}
+		x0 = x2;
+		f0 = f2;
+	}
+	Ok(roots)
+}
+
+pub fn area(xi_str: &RawStr, xf_str: &RawStr, f_str: &RawStr, g_str: &RawStr) -> Result<Results, String> {
+	let (xi, xf) = match limits(xi_str, xf_str) {
+		Ok(limits) => limits,
+		Err(message) => return Err(message),
+	};
+	let difference = |x| -> Result<f64, String> {
+		match (helper::function1(f_str.to_string(), x), helper::function1(g_str.to_string(), x)) {
+			(Ok(f), Ok(g)) => Ok(f - g),
+			(Err(message), _) | (_, Err(message)) => Err(message),
+		}
+	};
+	let intersections = match roots(&difference, xi, xf) {
+		Ok(intersections) => intersections,
+		Err(message) => return Err(message),
+	};
+	// The difference has a single sign between successive intersections.
+	let mut bounds = vec![xi];
+	bounds.extend(intersections.iter().cloned());
+	bounds.push(xf);
+	let integrand = format!("({}) - ({})", display(f_str), display(g_str));
+	let setup = if intersections.is_empty() {
+		format!("|integral from {} to {} of {} dx|", xi, xf, integrand)
+	} else {
+		let pieces: Vec<String> = bounds.windows(2).map(|pair| format!("|integral from {} to {} of {} dx|", pair[0], pair[1], integrand)).collect();
+		pieces.join(" + ")
+	};
+	let mut area = Quantity {name: "area".to_string(), setup, value: 0., error: 0., converged: true};
+	for pair in bounds.windows(2) {
+		match integration::integrate(&difference, pair[0], pair[1], &settings()) {
+			Ok(estimate) => {
+				area.value += estimate.integral.abs();
+				area.error += estimate.error;
+				area.converged = area.converged && estimate.converged;
+			},
+			Err(message) => return Err(message),
+		}
+	}
+	Ok(Results {xi, xf, axis: None, intersections, quantities: vec![area]})
+}
+
+pub fn revolution(axis_str: &RawStr, xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let axis = axis_str.to_lowercase();
+	if axis != "x" && axis != "y" {
+		return Err(format!("{} is not an axis.  Use either x or y.", axis_str));
+	}
+	let (xi, xf) = match limits(xi_str, xf_str) {
+		Ok(limits) => limits,
+		Err(message) => return Err(message),
+	};
+	// The shells from either side of the y-axis would overlap, and adding them would count the overlap twice.
+	if axis == "y" && xi.min(xf) < 0. && xi.max(xf) > 0. {
+		return Err(format!("The limits {} and {} lie on opposite sides of the y-axis, about which the two parts of the region would sweep out overlapping solids.", xi, xf));
+	}
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let expression = display(input_str);
+	let pi = std::f64::consts::PI;
+	// The radius of revolution is |f(x)| about the x-axis and |x| about the y-axis.
+	let (volume_setup, surface_setup) = if axis == "x" {(
+		format!("pi * integral from {} to {} of ({})^2 dx", xi, xf, expression),
+		format!("2 pi * integral from {} to {} of |{}| sqrt(1 + (f'(x))^2) dx", xi, xf, expression),
+	)} else {(
+		format!("2 pi * integral from {} to {} of |x| |{}| dx", xi, xf, expression),
+		format!("2 pi * integral from {} to {} of |x| sqrt(1 + (f'(x))^2) dx", xi, xf),
+	)};
+	let volume_integrand = |x: f64| -> Result<f64, String> {
+		match function(x) {
+			Ok(f) => Ok(if axis == "x" {f * f} else {(x * f).abs()}),
+			Err(message) => Err(message),
+		}
+	};
+	let surface_integrand = |x: f64| -> Result<f64, String> {
+		let radius = if axis == "x" {
+			match function(x) {
+				Ok(f) => f.abs(),
+				Err(message) => return Err(message),
+			}
+		} else {
+			x.abs()
+		};
+		match stretch(&function, x) {
+			Ok(stretch) => Ok(radius * stretch),
+			Err(message) => Err(message),
+		}
+	};
+	let mut quantities = vec![];
+	for (name, setup, factor, integrand) in [
+		("volume", volume_setup, if axis == "x" {pi} else {2. * pi}, &volume_integrand as &dyn Fn(f64) -> Result<f64, String>),
+		("surface area", surface_setup, 2. * pi, &surface_integrand),
+	] {
+		quantities.push(match quantity(name, setup, factor, integrand, xi, xf) {
+			Ok(quantity) => quantity,
+			Err(message) => return Err(message),
+		});
+	}
+	Ok(Results {xi, xf, axis: Some(axis), intersections: vec![], quantities})
+}
@@ -71,6 +71,99 @@ pub fn function3(mut expression: String, x: f64, t: f64, v: f64) -> Result<f64,
 	parse_expression(expression.to_string())
 }
 
+// names of the variables of a multivariable function, in order
+pub const VARIABLES: [&str; 4] = ["x", "y", "z", "w"];
+
+// names which may contain the same letters as variables, but which must be left intact
+const NAMES: [&str; 36] = [
+	"abs", "acos", "acosh", "acot", "acoth", "acsc", "acsch", "asec", "asech", "asin", "asinh",
+	"atan", "atanh", "cbrt", "ceil", "cos", "cot", "csc", "exp", "exp2", "exp_m1", "floor",
+	"fract", "ln", "ln_1p", "log10", "log2", "round", "sec", "signum", "sin", "sqrt", "tan",
+	"trunc", "pi", "div",
+];
+
+// the longest of the candidate names with which an expression starts
+fn longest<'a>(expression: &str, candidates: &[&'a str]) -> Option<&'a str> {
+	candidates.iter().cloned().filter(|name| expression.starts_with(*name)).max_by_key(|name| name.len())
+}
+
+// Replaces each variable in an expression by its parenthesized value, while skipping over the
+// names of functions and constants (eg, the "t" in "sqrt").
+pub fn substitute(expression: &str, names: &[&str], values: &[f64]) -> String {
+	let replacements: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+	replace(expression, names, &replacements)
+}
+
+fn replace(expression: &str, names: &[&str], replacements: &[String]) -> String {
+	let mut result = "".to_string();
+	let mut rest = expression;
+	while !rest.is_empty() {
+		// Longer names take priority (eg, "acosh" over "acos", and "x10" over "x1").
+		if let Some(name) = longest(rest, &NAMES) {
+			result += name;
+			rest = &rest[name.len()..];
+		} else if let Some(name) = longest(rest, names) {
+			let i = names.iter().position(|other| *other == name).unwrap();
+			result += &format!("({})", replacements[i]);
+			rest = &rest[name.len()..];
+		} else {
+			let char = rest.chars().next().unwrap();
+			result.push(char);
+			rest = &rest[char.len_utf8()..];
+		}
+	}
+	result
+}
+
+pub fn function(mut expression: String, names: &[&str], values: &[f64]) -> Result<f64, String> {
+	expression = expression.to_lowercase();
+	// following are replacements of url encoding of ^ and space, respectively.
+	expression = str::replace(&expression, "%5", "^");
+	expression = str::replace(&expression, "%20", "");
+	parse_expression(substitute(&expression, names, values))
+}
+
+// a complex number, for the complex-step method of differentiation
+#[derive(Clone, Copy, Debug)]
+pub struct Complex {
+	pub re: f64,
+	pub im: f64,
+}
+
+// placeholder for the complex value of the variable, which cannot be written as a number
+const VARIABLE: char = '@';
+
+// Evaluates a function of x for complex x.  Only the real part of x may be large, because the
+// imaginary part of each unary function (and of powers) is calculated to first order.
+pub fn complex_function1(mut expression: String, x: Complex) -> Result<Complex, String> {
+	expression = expression.to_lowercase();
+	// following are replacements of url encoding of ^ and space, respectively.
+	expression = str::replace(&expression, "%5", "^");
+	expression = str::replace(&expression, "%20", "");
+	parse_complex(replace(&expression, &["x"], &[VARIABLE.to_string()]), Some(x))
+}
+
+// Parses a value on the extended real line, which may be infinite.
+pub fn parse_extended(value_str: &str) -> Result<f64, String> {
+	match value_str.to_lowercase().as_str() {
+		"inf" | "+inf" | "infinity" | "+infinity" => Ok(f64::INFINITY),
+		"-inf" | "-infinity" => Ok(f64::NEG_INFINITY),
+		_ => parse_expression(value_str.to_string()),
+	}
+}
+
+// Parses a comma-separated list of expressions, such as the coordinates of a point.
+pub fn parse_list(list_str: &str) -> Result<Vec<f64>, String> {
+	let mut values = vec![];
+	for value_str in list_str.split(',') {
+		values.push(match parse_expression(value_str.to_string()) {
+			Ok(value) => value,
+			Err(message) => return Err(message),
+		});
+	}
+	Ok(values)
+}
+
 fn find_size (expression: &str) -> Result<usize, String> {
 	let mut n_paren = 1; // leading (open)paren has been found, in calling function
 	for (n_expression, char) in expression.chars().enumerate() {
@@ -83,12 +176,15 @@ fn find_size (expression: &str) -> Result<usize, String> {
 	Err(format!("Error: no closing parenthesis was found for this string: {}", expression))
 }
 
-fn get_value(expression: &mut String) -> Result<f64, String> {
+fn get_value(expression: &mut String, variable: Option<Complex>) -> Result<Complex, String> {
 	if expression.is_empty() {
 		return Err("Error: your expression truncates prematurely.".to_string());
 	}
-	let mut value = 0.;
-	if expression.starts_with('(') {
+	let mut value = Complex {re: 0., im: 0.};
+	if let Some(v) = variable.filter(|_| expression.starts_with(VARIABLE)) {
+		expression.remove(0);
+		value = v;
+	} else if expression.starts_with('(') {
 		// remove leading parenthesis
 		expression.remove(0);
 		let n_expression = match find_size(expression) {
@@ -96,7 +192,7 @@ fn get_value(expression: &mut String) -> Result<f64, String> {
 			Err(message) => return Err(message),
 		};
 		// recursive call to evaluate what is in parentheses
-		value = match parse_expression((&expression[..n_expression]).to_string()) {
+		value = match parse_complex((&expression[..n_expression]).to_string(), variable) {
 			Err(message) => return Err(message),
 			Ok(value) => value,
 		};
@@ -123,11 +219,11 @@ fn get_value(expression: &mut String) -> Result<f64, String> {
 			Err(message) => return Err(format!("Error: could not find length of argument string ({}) for function ({}): {}", expression, method, message)),
 		};
 		// recursive call, for argument of unary
-		let arg = match parse_expression((expression)[..n_expression].to_string()) {
+		let arg = match parse_complex((expression)[..n_expression].to_string(), variable) {
 			Ok(arg) => arg,
 			Err(message) => return Err(format!("Error: could not parse argument {}: {}", expression, message)),
 		};
-		value = match unary(&method, arg) {
+		value = match complex_unary(&method, arg) {
 			Ok(value) => value,
 			Err(message) => return Err(message),
 		};
@@ -140,7 +236,7 @@ fn get_value(expression: &mut String) -> Result<f64, String> {
 		while expression.len() >= p {
 			x = &expression[..p];
 			if !(x == "." || x == "-" || x == "-.") { // It's premature to parse for a number.
-				value = match x.parse() {
+				value.re = match x.parse() {
 					Ok(value) => {
 						found_value = true;
 						value // This may get more digit(s) in next iteration(s).
@@ -151,7 +247,7 @@ fn get_value(expression: &mut String) -> Result<f64, String> {
 			p += 1;
 		}
 		if x.starts_with("-") && p == 2 && expression.len() > 1 { // examples of this edge case: -sin(x) or -(x+1)**2
-			value = -1.;
+			value.re = -1.;
 			found_value = true;
 		}
 		if !found_value {
@@ -162,6 +258,46 @@ fn get_value(expression: &mut String) -> Result<f64, String> {
 	Ok(value)
 }
 
+// Arithmetic is exact, except that the imaginary part of a complex power is first-order.
+fn complex_binary(z1: Complex, op: &char, z2: Complex) -> Result<Complex, String> {
+	if z1.im == 0. && z2.im == 0. {
+		return match binary(z1.re, op, z2.re) {
+			Ok(re) => Ok(Complex {re, im: 0.}),
+			Err(message) => Err(message),
+		};
+	}
+	let Complex {re: a, im: b} = z1;
+	let Complex {re: c, im: d} = z2;
+	let z = match op {
+		'+' => Complex {re: a + c, im: b + d},
+		'-' => Complex {re: a - c, im: b - d},
+		'*' => Complex {re: a * c - b * d, im: a * d + b * c},
+		'/' => {
+			let size = c * c + d * d;
+			if size == 0. {
+				return Err(format!("Error: {}/0 signifies an attempt to divide by zero", a));
+			}
+			Complex {re: (a * c + b * d) / size, im: (b * c - a * d) / size}
+		},
+		'^' => {
+			let re = match binary(a, op, c) {
+				Ok(re) => re,
+				Err(message) => return Err(message),
+			};
+			let mut im = if b == 0. {0.} else {b * c * a.powf(c - 1.)};
+			if d != 0. {
+				if a <= 0. {
+					return Err(format!("Error: {} may not be raised to a complex power.", a));
+				}
+				im += d * re * a.ln();
+			}
+			Complex {re, im}
+		},
+		_ => unreachable!(),
+	};
+	Ok(z)
+}
+
 fn binary(x1: f64, op: &char, x2: f64) -> Result<f64, String> {
 	let x = match op {
 		'+' => x1 + x2,
@@ -184,7 +320,15 @@ fn binary(x1: f64, op: &char, x2: f64) -> Result<f64, String> {
 	Ok(x)
 }
 
-pub fn parse_expression(mut expression: String) -> Result<f64, String> {
+pub fn parse_expression(expression: String) -> Result<f64, String> {
+	match parse_complex(expression, None) {
+		Ok(value) => Ok(value.re),
+		Err(message) => Err(message),
+	}
+}
+
+// The variable is the value which replaces any placeholder in the expression.
+fn parse_complex(mut expression: String, variable: Option<Complex>) -> Result<Complex, String> {
 	preparse(&mut expression, 0.);
 	expression = str::replace(&expression, "pi", &format!("({})", PI)); // important constant
   	for stri in ["div", "DIV", "d", "D"] {
@@ -202,7 +346,7 @@ pub fn parse_expression(mut expression: String) -> Result<f64, String> {
 	let mut vals = vec![];
 	let mut ops = vec![];
 	// trim & push leading number from expression
-	vals.push(match get_value(&mut expression) {
+	vals.push(match get_value(&mut expression, variable) {
 		Err(message) => return Err(message),
 		Ok(value) => value,
 	});
@@ -212,7 +356,7 @@ pub fn parse_expression(mut expression: String) -> Result<f64, String> {
 		let op = expression.chars().next().unwrap();
 		// The following ternary includes an implied multiplication, if appropriate.
 		ops.push(if op_string.contains(op) {expression.remove(0)} else {'*'});
-		vals.push(match get_value(&mut expression) {
+		vals.push(match get_value(&mut expression, variable) {
 			Err(message) => return Err(message),
 			Ok(value) => value,
 		});
@@ -226,7 +370,7 @@ pub fn parse_expression(mut expression: String) -> Result<f64, String> {
 				index += 1;
 			} else {
 				// perform this operation NOW, because of PEMDAS rule
-				match binary(vals[index], &ops[index], vals[index + 1]) {
+				match complex_binary(vals[index], &ops[index], vals[index + 1]) {
 					Err(message) => return Err(message),
 					Ok(result) => {
 						// mutate vals & ops (including the shortening of both by one)
@@ -247,6 +391,60 @@ fn is_nonzero(x: f64) -> Result<f64, String> {
 	if x == 0. {Err("Error: divide by zero".to_string())} else {Ok(x)}
 }
 
+// A unary function of a complex argument a + ib is approximated as f(a) + ib f'(a).
+fn complex_unary(method: &str, z: Complex) -> Result<Complex, String> {
+	let re = match unary(method, z.re) {
+		Ok(re) => re,
+		Err(message) => return Err(message),
+	};
+	let im = if z.im == 0. {0.} else {
+		match unary_derivative(method, z.re) {
+			Ok(deriv) => z.im * deriv,
+			Err(message) => return Err(message),
+		}
+	};
+	Ok(Complex {re, im})
+}
+
+fn unary_derivative(method: &str, x: f64) -> Result<f64, String> {
+	let deriv = match method {
+		"abs" | "signum" => if x == 0. {f64::NAN} else if method == "abs" {x.signum()} else {0.},
+		"acos" => -1. / (1. - x * x).sqrt(),
+		"acosh" => 1. / (x * x - 1.).sqrt(),
+		"acot" => -1. / (1. + x * x),
+		"acoth" | "atanh" => 1. / (1. - x * x),
+		"acsc" => -1. / (x.abs() * (x * x - 1.).sqrt()),
+		"acsch" => -1. / (x.abs() * (1. + x * x).sqrt()),
+		"asec" => 1. / (x.abs() * (x * x - 1.).sqrt()),
+		"asech" => -1. / (x * (1. - x * x).sqrt()),
+		"asin" => 1. / (1. - x * x).sqrt(),
+		"asinh" => 1. / (x * x + 1.).sqrt(),
+		"atan" => 1. / (1. + x * x),
+		"cbrt" => 1. / (3. * x.cbrt().powi(2)),
+		"ceil" | "floor" | "round" | "trunc" => if x.fract() == 0. || (method == "round" && x.fract().abs() == 0.5) {f64::NAN} else {0.},
+		"fract" => if x.fract() == 0. {f64::NAN} else {1.},
+		"cos" => -x.sin(),
+		"cot" => -1. / x.sin().powi(2),
+		"csc" => -x.cos() / x.sin().powi(2),
+		"exp" | "exp_m1" => x.exp(),
+		"exp2" => std::f64::consts::LN_2 * x.exp2(),
+		"ln" => 1. / x,
+		"ln_1p" => 1. / (1. + x),
+		"log10" => 1. / (x * std::f64::consts::LN_10),
+		"log2" => 1. / (x * std::f64::consts::LN_2),
+		"sec" => x.sin() / x.cos().powi(2),
+		"sin" => x.cos(),
+		"sqrt" => 0.5 / x.sqrt(),
+		"tan" => 1. / x.cos().powi(2),
+		_ => return Err(format!("Error: no such function: {}", method)),
+	};
+	if deriv.is_finite() {
+		Ok(deriv)
+	} else {
+		Err(format!("Error: {} is not differentiable at {}.", method, x))
+	}
+}
+
 fn unary(method: &str, x: f64) -> Result<f64, String> {
 	let negative = format!("is not defined for negative argument such as {}", x);
 	let nonpositive = format!("is not defined for a nonpositive argument such as {}", x);
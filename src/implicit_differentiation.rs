@@ -0,0 +1,86 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::partial_differentiation::{self, Estimate};
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "IMPLICIT DIFFERENTIATION".to_string(),
+		links: calculus::links(12),
+		instructions: "This page calculates the first and second derivatives of <i>y</i> with respect to <i>x</i> along a curve which is defined implicitly by an equation <i>F</i>(<i>x</i>, <i>y</i>) = 0.  In the url bar after <tt>https://basic-calculus.herokuapp.com/implicit-differentiation</tt> type the following:<p align=center><tt>&sol;&lt;<i>x</i>&gt;,&lt;<i>y</i>&gt;&sol;&lt;equation in <i>x</i> and <i>y</i>&gt;</tt></p>The equation may either be written as <tt>&lt;left side&gt;=&lt;right side&gt;</tt> or else be an expression, which is then set equal to 0.  If the point does not lie on the curve, the results are those for the curve along which <i>F</i> equals its value at that point.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the coordinates of the point)", helper::NOTE2),
+		example: "To differentiate implicitly the equation <i>x</i><sup>2</sup> + <i>y</i><sup>3</sup> = 2<i>xy</i> at (<i>x</i>, <i>y</i>) = (1, 1), type <tt>/1,1/x**2+y**3=2xy</tt> after the current url address.  The results for <i>dy</i>/<i>dx</i> and <i>d</i><sup>2</sup><i>y</i>/<i>dx</i><sup>2</sup> should be <tt>0</tt> and <tt>-2</tt>.".to_string(),
+		algorithm: "<i>dy</i>/<i>dx</i> = -<i>F<sub>x</sub></i>/<i>F<sub>y</sub></i> and <i>d</i><sup>2</sup><i>y</i>/<i>dx</i><sup>2</sup> = -(<i>F<sub>xx</sub>F<sub>y</sub></i><sup>2</sup> - 2<i>F<sub>xy</sub>F<sub>x</sub>F<sub>y</sub></i> + <i>F<sub>yy</sub>F<sub>x</sub></i><sup>2</sup>)/<i>F<sub>y</sub></i><sup>3</sup>, with the partial derivatives calculated as on the partial differentiation page".to_string(),
+		json: "Type '/json' in the url bar immediately after 'implicit-differentiation' if you would like the result in this format rather than html.  A successful response will contain four properties: 'point' (an array of two floats), 'value' (the value of <i>F</i> there, which should be 0), and 'first' and 'second' (objects whose properties 'value' and 'error' are the first or second derivative and its estimated absolute error).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub point: Vec<f64>,
+	pub value: f64,
+	pub first: Estimate,
+	pub second: Estimate,
+}
+
+// An equation is converted to an expression which vanishes on the curve.
+fn expression(input: &str) -> Result<String, String> {
+	let sides: Vec<&str> = input.split('=').collect();
+	match sides.len() {
+		1 => Ok(input.to_string()),
+		2 => Ok(format!("({})-({})", sides[0], sides[1])),
+		_ => Err("An equation may have only one equals sign.".to_string()),
+	}
+}
+
+pub fn raw(point_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let point = match helper::parse_list(&point_str.to_string()) {
+		Ok(point) => point,
+		Err(message) => return Err(message),
+	};
+	if point.len() != 2 {
+		return Err("The point must have two coordinates: x and y.".to_string());
+	}
+	let input = match expression(&input_str.to_string()) {
+		Ok(input) => input,
+		Err(message) => return Err(message),
+	};
+	let names = &helper::VARIABLES[..2];
+	let function = |coords: &[f64]| helper::function(input.clone(), names, coords);
+	let value = match function(&point) {
+		Ok(value) => value,
+		Err(message) => return Err(message),
+	};
+	// partial derivatives, with orders w.r.t. x and y
+	let mut partials = vec![];
+	for orders in &[[1, 0], [0, 1], [2, 0], [1, 1], [0, 2]] {
+		partials.push(match partial_differentiation::partial(&function, &point, orders) {
+			Ok(estimate) => estimate,
+			Err(message) => return Err(message),
+		});
+	}
+	let (fx, fy, fxx, fxy, fyy) = (&partials[0], &partials[1], &partials[2], &partials[3], &partials[4]);
+	if fy.value == 0. || fy.value.abs() <= fy.error {
+		return Err(format!("The tangent to the curve at ({}, {}) is vertical, because the derivative of the function with respect to y vanishes there.", point[0], point[1]));
+	}
+	let first = -fx.value / fy.value;
+	let numerator = fxx.value * fy.value.powi(2) - 2. * fxy.value * fx.value * fy.value + fyy.value * fx.value.powi(2);
+	let second = -numerator / fy.value.powi(3);
+	// Errors are propagated to first order in those of the partial derivatives.
+	let first_error = (fx.error + first.abs() * fy.error) / fy.value.abs();
+	let numerator_error = fxx.error * fy.value.powi(2)
+		+ 2. * fxy.error * (fx.value * fy.value).abs()
+		+ fyy.error * fx.value.powi(2)
+		+ fx.error * 2. * (fxy.value * fy.value).abs() + fx.error * 2. * (fyy.value * fx.value).abs()
+		+ fy.error * 2. * (fxx.value * fy.value).abs() + fy.error * 2. * (fxy.value * fx.value).abs();
+	let second_error = (numerator_error + 3. * second.abs() * fy.value.powi(2) * fy.error) / fy.value.abs().powi(3);
+	Ok(Results {
+		point,
+		value,
+		first: Estimate {value: first, error: first_error},
+		second: Estimate {value: second, error: second_error},
+	})
+}
@@ -2,16 +2,17 @@ use rocket::http::RawStr;
 use serde::{Serialize, Deserialize};
 
 use crate::helper;
+use crate::differentiation;
 
 fn instructions() -> helper::LongPage {
 	helper::LongPage {
 		title: "INTEGRATION".to_string(),
 		links: calculus::links(3),
-		instructions: "In the url bar after <tt>'https://basic-calculus.herokuapp.com/integration</tt> type the following:<p align=center>&sol;&lt;lower limit of integration&gt;&sol;&lt;upper limit of integration&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Neither singularities (integrable or otherwise) nor infinite ranges of integration are allowed.".to_string(),
+		instructions: "In the url bar after <tt>'https://basic-calculus.herokuapp.com/integration</tt> type the following:<p align=center>&sol;&lt;lower limit of integration&gt;&sol;&lt;upper limit of integration&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Either limit may be <tt>inf</tt> or <tt>-inf</tt>.  An integrable singularity is allowed at either endpoint, but not between them.  However the Cauchy principal value of an integral whose range contains simple poles (at which the function diverges with opposite signs on either side) may be calculated by appending <tt>?pv=&lt;comma-separated list of the poles&gt;</tt> to the url.  By default the integral is calculated by Simpson's rule, but you may instead append <tt>?method=&lt;rule&gt;</tt> to the url, in which the rule is <tt>trapezoid</tt>, <tt>simpson</tt>, <tt>romberg</tt>, <tt>gauss-legendre</tt> (with 5 points per subdivision, unless you also append <tt>?points=&lt;number no greater than 20&gt;</tt>), <tt>gk15</tt> or <tt>gk21</tt> (adaptive Gauss-Kronrod rules, which subdivide the range only where the function requires it), or <tt>tanh-sinh</tt>.  The calculation stops when the estimated error is no larger than an absolute tolerance of 10<sup>-12</sup>, which you may change by appending (for instance) <tt>?epsilon=10**-6</tt>, or else no larger than a relative tolerance (0 by default) which is set with <tt>?rel_epsilon</tt>.  It also stops, with the best estimate so far, when the number of subdivisions would exceed a cap which is set with <tt>?max_subdivisions</tt> (by default 1048576 for the trapezoidal, Simpson's, and Romberg's rules, 4096 for the Gauss-Legendre rule, 32768 for the tanh-sinh rule, and 1000 for the Gauss-Kronrod rules), which may be no greater than 16777216, 65536, 1048576, and 5000, respectively.  (Filon's rule shares both numbers with Simpson's.)  When several rules are compared, each uses the smaller of the cap and its own limit.  To also see the value of Simpson's rule (with and without Aitken's correction) after each doubling of the number of subdivisions, append <tt>?history=true</tt>.  Separate these settings with <tt>&amp;</tt>.<br>To compare the results of several rules, instead type the following:<p align=center><tt>&sol;compare&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>By default every rule is used, but you may append <tt>?methods=&lt;comma-separated list of rules&gt;</tt> to the url, as well as any of the settings above except <tt>method</tt>, <tt>pv</tt>, and <tt>history</tt>.  A rule which would evaluate the function at an endpoint where it cannot be evaluated is reported as such, rather than being replaced by the tanh-sinh rule.<br>To tabulate the cumulative integral <i>F</i>(<i>x</i>) = &int;<i>f</i> from the lower limit to each of a set of equally spaced points, instead type the following:<p align=center><tt>&sol;cumulative&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;number of intervals (no more than 1000)&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>in which case both limits must be finite, and the rule must be <tt>simpson</tt> (the default), <tt>gk15</tt>, or <tt>gk21</tt>.<br>To integrate the product of a smooth function <i>f</i>(<i>x</i>) and sin(&omega;<i>x</i>) or cos(&omega;<i>x</i>), for which &omega; may be so large that the product oscillates many times between the limits, instead type the following:<p align=center><tt>&sol;oscillatory&sol;&lt;sin or cos&gt;&sol;&lt;&omega;&gt;&sol;&lt;lower limit&gt;&sol;&lt;upper limit&gt;&sol;&lt;function <i>f</i> of <i>x</I>&gt;</tt></p>in which case both limits must be finite, and only the settings <tt>epsilon</tt>, <tt>rel_epsilon</tt>, and <tt>max_subdivisions</tt> apply.".to_string(),
 		note: format!("{}{}{}", helper::NOTE1, "", helper::NOTE2).to_string(),
-		example: "To integrate the function 2<i>x</i> + 3/(<i>x</i><sup>4</sup> + 5) from <i>x</i> = 1 to 6, type <tt>/1/6/2x+3d(x**4+5)</tt> after the current url address.  The result for this should be <tt>35.41...</tt>".to_string(),
-		algorithm: "composite Simpson's rule and Aitken extrapolation".to_string(),
-		json: "Type '/json' in the url bar immediately after 'integration' if you would like the result in this format rather than html.  A successful response will contain five properties. 'xi' and 'xf' are the lower and upper limits of integration, 'integral' is the value of the definite integral, and 'subdivisions' is the number of equally sized intervals into which the range of integration needed to be subdivided in order to achieve the absolute accuracy specified in the last property: 'epsilon'. An unsuccessful response will have one property: 'message' (a string reporting the error)".to_string(),
+		example: "To integrate the function 2<i>x</i> + 3/(<i>x</i><sup>4</sup> + 5) from <i>x</i> = 1 to 6, type <tt>/1/6/2x+3d(x**4+5)</tt> after the current url address.  The result for this should be <tt>35.41...</tt>  Typing <tt>/-inf/inf/exp(-x**2)</tt> should give <tt>1.772...</tt> (ie, &radic;&pi;), and typing <tt>/0/1/1dsqrt(x)</tt> should give <tt>2</tt>.  Typing <tt>/-1/1/1d(1+10000x**2)?method=gk21</tt> should give <tt>0.0312...</tt> after far fewer evaluations than Simpson's rule requires, typing <tt>/compare/0/1/exp(x)</tt> should show how many evaluations each rule requires for the same accuracy, and typing <tt>/0/3/1d(x-1)?pv=1</tt> should give the principal value <tt>0.6931...</tt> (ie, ln 2).  Typing <tt>/cumulative/0/3.14159/4/sin(x)</tt> will tabulate 1 - cos(<i>x</i>) at <i>x</i> = 0, 0.785..., 1.570..., 2.356..., and 3.141...  Typing <tt>/oscillatory/cos/1000/0/1/exp(x)</tt> should give the integral of <i>e</i><sup><i>x</i></sup>cos(1000<i>x</i>): <tt>0.002248...</tt>".to_string(),
+		algorithm: "by default, composite Simpson's rule and Aitken extrapolation.  The trapezoidal, Romberg's, and Gauss-Legendre rules are also composite, with the number of subdivisions doubling until successive values agree.  Romberg's rule extrapolates the successive values of the trapezoidal rule by Richardson's method, and the nodes of the Gauss-Legendre rule are the roots of a Legendre polynomial, found by Newton's method.  An infinite range is first mapped onto a finite one by the substitution <i>x</i> = <i>t</i>/(1 - <i>t</i><sup>2</sup>) or <i>x</i> = <i>a</i> &plusmn; <i>t</i>/(1 - <i>t</i>), with the transformed integrand at the image of &plusmn;&infin; found by extrapolation.  If the function cannot be evaluated at an endpoint, the tanh-sinh rule is used instead of any rule which would evaluate it there: the substitution <i>x</i> = <i>c</i> + <i>d</i> tanh(&pi; sinh(<i>t</i>)/2) followed by the trapezoidal rule in <i>t</i>, which never refers to the endpoints themselves.  The Gauss-Kronrod rules use 15 or 21 points per interval, and the difference between each and its embedded 7- or 10-point Gauss rule estimates the error, which determines the interval to be bisected next.  For a principal value, the integral over an interval of half-width <i>h</i> about each pole <i>c</i> is rewritten as &int;<sub>0</sub><sup><i>h</i></sup>[<i>f</i>(<i>c</i> + <i>u</i>) + <i>f</i>(<i>c</i> - <i>u</i>)] <i>du</i>, in which the singular terms cancel, and the rest of the range is integrated as usual.  A cumulative table is calculated in a single pass over the whole range, either by Simpson's rule with the same number of subdivisions in each interval of the table or by a Gauss-Kronrod rule which starts from those intervals, and the integrals over the intervals are then added in turn.  Oscillatory integrals are calculated by Filon's rule, which is like Simpson's rule except that it integrates exactly the product of the weight and the parabola through each pair of intervals, so that it remains accurate even when each interval contains many periods of the weight".to_string(),
+		json: "Type '/json' in the url bar immediately after 'integration' if you would like the result in this format rather than html.  A successful response will contain fourteen properties. 'xi' and 'xf' are the lower and upper limits of integration (null if infinite), 'integral' is the value of the definite integral, 'error' is its estimated absolute error, 'method' is the rule used ('trapezoid', 'simpson', 'romberg', 'gauss-legendre', 'gk15', 'gk21', 'tanh-sinh', or 'filon'), 'subdivisions' is the number of intervals into which the range of integration (or, for the tanh-sinh rule, that of the transformed variable) needed to be subdivided, and 'evaluations' is the number of times that the function was evaluated, in order to achieve the absolute or relative accuracy specified in 'epsilon' and 'rel_epsilon'.  'max_subdivisions' is the cap on the number of subdivisions (null for the default), and 'converged' is false if the estimated error exceeds that accuracy (because the cap was reached first, or because roundoff prevents any further improvement), in which case 'integral' is the best estimate so far.  'pv' is the array of poles for a principal value (null otherwise), in which case 'method' lists the rules used, separated by '+'.  'points' is the number of points per subdivision of the Gauss-Legendre rule (null for other rules), and 'history' is null unless <tt>?history=true</tt> is appended to the url, in which case it is an array of objects, one for each successive doubling of the number of subdivisions of Simpson's rule, with properties 'subdivisions', 'simpson' (the value of the rule), 'aitken' (that value after Aitken's correction), and 'difference' (the absolute difference from the previous corrected value, or null for the first), so that the rate of convergence may be seen.  (The array is empty if any other rule was used, or for a principal value.)  For a comparison, type '/json' immediately after 'compare'.  A successful response will contain seven properties: 'xi', 'xf', 'epsilon', 'rel_epsilon', 'max_subdivisions', 'points', and 'rules' (an array of objects, each with properties 'method', 'integral', 'error', 'subdivisions', 'evaluations', and 'converged' as above, and 'message', which is null unless the rule could not be used, in which case it reports why and 'integral' and 'error' are null).  For an oscillatory integral, type '/json' immediately after 'oscillatory', and the response will be similar (with a 'method' of 'filon').  For a cumulative table, type '/json' or '/csv' immediately after 'cumulative'.  A successful json response will contain six properties: 'xi', 'xf', 'n' (the number of intervals), 'evaluations', 'converged', and 'rows' (an array of n + 1 objects, each with properties 'x', 'integral' (the integral from xi to x), and 'error' (its estimated absolute error)). An unsuccessful response will have one property: 'message' (a string reporting the error)".to_string(),
 	}
 }
 
@@ -22,28 +23,132 @@ pub struct Results {
 	pub xi: f64,
 	pub xf: f64,
 	pub integral: f64,
+	pub error: f64,
+	pub method: String,
 	pub subdivisions: i32,
+	pub evaluations: i32,
 	pub epsilon: f64,
+	pub rel_epsilon: f64,
+	pub max_subdivisions: Option<i32>,
+	pub converged: bool,
+	pub pv: Option<Vec<f64>>,
+	pub points: Option<i32>,
+	pub history: Option<Vec<Level>>,
 }
 
-pub fn raw(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
-	let epsilon = (10_f64).powf(-12.);
+// choices which affect how an integral is calculated
+pub struct Settings {
+	pub epsilon: f64,
+	pub rel_epsilon: f64,
+	pub max_subdivisions: Option<i32>, // None means the default for the method
+	pub method: String,
+	pub points: i32, // number of points per interval for the Gauss-Legendre rule
+	pub fallback: bool, // whether tanh-sinh replaces a rule which cannot evaluate the function at an endpoint
+}
+
+impl Default for Settings {
+	fn default() -> Settings {
+		Settings {epsilon: EPSILON, rel_epsilon: 0., max_subdivisions: None, method: "simpson".to_string(), points: POINTS, fallback: true}
+	}
+}
+
+impl Settings {
+	// the accuracy required of an integral with (roughly) this value
+	pub fn tolerance(&self, integral: f64) -> f64 {
+		self.epsilon.max(self.rel_epsilon * integral.abs())
+	}
+	// the cap for a rule, which is never more than the rule's limit even when another rule was requested
+	fn max_subdivisions(&self, default: i32, limit: i32) -> i32 {
+		self.max_subdivisions.unwrap_or(default).min(limit)
+	}
+}
+
+// one of the successive refinements of Simpson's rule
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Level {
+	pub subdivisions: i32,
+	pub simpson: f64,
+	pub aitken: f64,
+	pub difference: Option<f64>, // None for the first level, which has nothing with which to compare
+}
+
+// the result of integrating a function by a particular rule
+pub struct Estimate {
+	pub integral: f64,
+	pub error: f64,
+	pub subdivisions: i32,
+	pub evaluations: i32,
+	pub method: String,
+	pub converged: bool, // false if the error exceeds the tolerance, in which case this is the best estimate so far
+	pub history: Vec<Level>, // empty except for Simpson's rule
+}
+
+impl Estimate {
+	// the estimate for the same range traversed in the opposite direction
+	fn negated(self) -> Estimate {
+		let history = self.history.into_iter().map(|level| Level {simpson: -level.simpson, aitken: -level.aitken, ..level}).collect();
+		Estimate {integral: -self.integral, history, ..self}
+	}
+}
+
+pub const EPSILON: f64 = 1e-12; // default absolute accuracy
+pub const MIN_SUBDIVISIONS: i32 = 16; // fewest subdivisions for which convergence is tested
+const T_MAX: f64 = 4.; // tanh-sinh points lie within this distance of 0 in the transformed variable
+// default caps on the number of subdivisions for the trapezoidal (and Romberg's and Simpson's),
+// Gauss-Legendre, tanh-sinh, and Gauss-Kronrod rules
+const MAX_SIMPSON: i32 = 1 << 20;
+const MAX_GAUSS_LEGENDRE: i32 = 1 << 12;
+const MAX_TANH_SINH: i32 = 1 << 15;
+const MAX_INTERVALS: i32 = 1000;
+// the largest caps which may be requested for each of these rules, beyond which the time taken would be unreasonable
+const LIMIT_SIMPSON: i32 = 1 << 24;
+const LIMIT_GAUSS_LEGENDRE: i32 = 1 << 16;
+const LIMIT_TANH_SINH: i32 = 1 << 20;
+const LIMIT_INTERVALS: i32 = 5000;
+const MAX_ROWS: i32 = 1000; // largest number of intervals in a cumulative table
+const POINTS: i32 = 5; // default number of points for the Gauss-Legendre rule
+const MAX_POINTS: i32 = 20;
+pub const METHODS: [&str; 7] = ["trapezoid", "simpson", "romberg", "gauss-legendre", "gk15", "gk21", "tanh-sinh"];
+
+// Nonnegative nodes (in decreasing order) and weights of the Kronrod rules, and the weights of
+// the Gauss rules which use every other node (See QUADPACK.)
+const XK15: [f64; 8] = [
+	0.9914553711208126, 0.9491079123427585, 0.8648644233597691, 0.7415311855993945,
+	0.5860872354676911, 0.4058451513773972, 0.20778495500789848, 0.,
+];
+const WK15: [f64; 8] = [
+	0.022935322010529224, 0.06309209262997856, 0.10479001032225019, 0.14065325971552592,
+	0.1690047266392679, 0.19035057806478542, 0.20443294007529889, 0.20948214108472782,
+];
+const WG7: [f64; 4] = [0.1294849661688697, 0.27970539148927664, 0.3818300505051189, 0.4179591836734694];
+const XK21: [f64; 11] = [
+	0.9956571630258081, 0.9739065285171717, 0.9301574913557082, 0.8650633666889845,
+	0.7808177265864169, 0.6794095682990244, 0.5627571346686047, 0.4333953941292472,
+	0.2943928627014602, 0.14887433898163122, 0.,
+];
+const WK21: [f64; 11] = [
+	0.011694638867371874, 0.032558162307964725, 0.054755896574351995, 0.07503967481091996,
+	0.0931254545836976, 0.10938715880229764, 0.12349197626206584, 0.13470921731147334,
+	0.14277593857706009, 0.14773910490133849, 0.1494455540029169,
+];
+const WG10: [f64; 5] = [0.06667134430868814, 0.1494513491505806, 0.21908636251598204, 0.26926671930999635, 0.29552422471475287];
+
+// Integrates a function over a finite range by the composite Simpson's rule, for which the number
+// of subdivisions doubles until successive Aitken-corrected values agree to within the tolerance
+// or until doubling would exceed the cap.
+pub fn simpson(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings) -> Result<Estimate, String> {
 	struct Pt {
 		x: f64,
 		f: f64,
 		wt: f64,
 	}
 	let mut pts = vec![];
-	for x_str in &[xi_str, xf_str] {
-		let x = match helper::parse_expression(x_str.to_string()) {
-			Ok(x) => x,
-			Err(message) => return Err(message),
-		};
-		let f = match helper::function1(input_str.to_string(), x) {
+	for x in &[xi, xf] {
+		let f = match function(*x) {
 			Ok(f) => f,
 			Err(message) => return Err(message),
 		};
-		pts.push(Pt{x, f, wt: 0.5}); // non-0th pt will only reside in vector for an instant
+		pts.push(Pt{x: *x, f, wt: 0.5}); // non-0th pt will only reside in vector for an instant
 	}
 	let ptf = match pts.pop() { // final point will be handled separately, going forward
 	  	Some(ptf) => ptf,
@@ -55,7 +160,12 @@ pub fn raw(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Resul
 	let mut aitkens_new = f64::INFINITY;
 	let mut dx = ptf.x - pts[0].x; // interval for Simpson's rule
 	let mut number = 1;
-	while !aitkens.is_finite() || !aitkens_new.is_finite() || (aitkens_new - aitkens).abs() > epsilon {
+	let mut history = vec![];
+	let max_subdivisions = settings.max_subdivisions(MAX_SIMPSON, LIMIT_SIMPSON);
+	// Coarse subdivisions of a symmetric integrand may agree by coincidence.
+	let converged = |number, aitkens: f64, aitkens_new: f64| number >= MIN_SUBDIVISIONS && aitkens.is_finite()
+		&& aitkens_new.is_finite() && (aitkens_new - aitkens).abs() <= settings.tolerance(aitkens_new);
+	while !converged(number, aitkens, aitkens_new) && number <= max_subdivisions / 2 {
 		number *= 2;
 		let mut integral_new = ptf.f * ptf.wt;
 		let mut new_pts = vec![];
@@ -64,7 +174,7 @@ pub fn raw(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Resul
 			integral_new += pt.f * pt.wt;
 			pt.wt = 1.; // wt for most points is 1 except for their first appearance
 			let x = pt.x + dx; // x-coord of next point
-			let f = match helper::function1(input_str.to_string(), x) {
+			let f = match function(x) {
 			  	Ok(f) => f,
 			  	Err(message) => return Err(format!("Cannot evaluate function at x: {}{}", pt.x, message)),
 			};
@@ -80,12 +190,1071 @@ pub fn raw(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> Result<Resul
 			aitkens_new += (integral_new - integral ) / (16. - 1.);
 		}
 		integral = integral_new;
+		history.push(Level {
+			subdivisions: number,
+			simpson: integral,
+			aitken: aitkens_new,
+			difference: if aitkens.is_finite() {Some((aitkens_new - aitkens).abs())} else {None},
+		});
 	}
-	Ok(Results{
+	Ok(Estimate {
 		integral: aitkens_new,
-		xi: pts[0].x,
-	  	xf: ptf.x,
+		error: (aitkens_new - aitkens).abs(),
 		subdivisions: number,
-		epsilon: epsilon,
+		evaluations: number + 1,
+		method: "simpson".to_string(),
+		converged: converged(number, aitkens, aitkens_new),
+		history,
+	})
+}
+
+// Integrates a function over a finite range by the composite trapezoidal rule, for which the number
+// of subdivisions doubles (reusing the previous points) until successive values agree to within
+// the tolerance or until doubling would exceed the cap.  For Romberg's method each new value is
+// also extrapolated by Richardson's method, as if its error were a series in even powers of the
+// width of the subdivisions.
+pub fn trapezoid(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings, romberg: bool) -> Result<Estimate, String> {
+	let evaluate = |x: f64| -> Result<f64, String> {
+		match function(x) {
+			Ok(f) => Ok(f),
+			Err(message) => Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+		}
+	};
+	let mut sum = 0.; // sum of the function values, with weights of 1/2 at the endpoints
+	for x in &[xi, xf] {
+		sum += match evaluate(*x) {
+			Ok(f) => f / 2.,
+			Err(message) => return Err(message),
+		};
+	}
+	let mut number = 1;
+	// the latest row of Romberg's table, whose first element is the trapezoidal rule
+	let mut row = vec![(xf - xi) * sum];
+	let mut difference = f64::INFINITY;
+	let mut converged = false;
+	let max_subdivisions = settings.max_subdivisions(MAX_SIMPSON, LIMIT_SIMPSON);
+	while !converged && number <= max_subdivisions / 2 {
+		let h = (xf - xi) / (2 * number) as f64;
+		// Only the odd-numbered points are new.
+		for j in 0..number {
+			sum += match evaluate(xi + (2 * j + 1) as f64 * h) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			};
+		}
+		number *= 2;
+		let mut new_row = vec![h * sum];
+		if romberg {
+			for j in 1..=row.len() {
+				let factor = 4f64.powi(j as i32);
+				new_row.push(new_row[j - 1] + (new_row[j - 1] - row[j - 1]) / (factor - 1.));
+			}
+		}
+		difference = (new_row[new_row.len() - 1] - row[row.len() - 1]).abs();
+		row = new_row;
+		// Coarse trapezoidal rules for a symmetric integrand may agree by coincidence, but successive
+		// extrapolations of Romberg's rule are of different orders.
+		converged = (romberg || number >= MIN_SUBDIVISIONS) && difference <= settings.tolerance(row[row.len() - 1]);
+	}
+	Ok(Estimate {
+		integral: row[row.len() - 1],
+		error: difference,
+		subdivisions: number,
+		evaluations: number + 1,
+		method: if romberg {"romberg"} else {"trapezoid"}.to_string(),
+		converged,
+		history: vec![],
+	})
+}
+
+// the nodes and weights of the n-point Gauss-Legendre rule on the interval from -1 to 1, for which
+// each node is a root of the Legendre polynomial of order n, found by Newton's method
+fn legendre(n: i32) -> Vec<(f64, f64)> {
+	let mut rule = vec![];
+	for i in 1..=n {
+		let mut x = (std::f64::consts::PI * (i as f64 - 0.25) / (n as f64 + 0.5)).cos();
+		let mut derivative = 1.;
+		for _ in 0..100 {
+			// the recurrence for the polynomials of orders n - 1 and n
+			let (mut p0, mut p1) = (1., x);
+			for k in 2..=n {
+				let p2 = ((2 * k - 1) as f64 * x * p1 - (k - 1) as f64 * p0) / k as f64;
+				p0 = p1;
+				p1 = p2;
+			}
+			derivative = if n == 1 {1.} else {n as f64 * (x * p1 - p0) / (x * x - 1.)};
+			let dx = p1 / derivative;
+			x -= dx;
+			if dx.abs() <= f64::EPSILON {
+				break;
+			}
+		}
+		rule.push((x, 2. / ((1. - x * x) * derivative * derivative)));
+	}
+	rule
+}
+
+// Integrates a function over a finite range by the composite Gauss-Legendre rule, for which the
+// number of subdivisions doubles until successive values agree to within the tolerance or until
+// doubling would exceed the cap.  No function is evaluated at an endpoint.
+pub fn gauss_legendre(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings) -> Result<Estimate, String> {
+	let rule = legendre(settings.points);
+	let composite = |number: i32| -> Result<f64, String> {
+		let half = (xf - xi) / (2 * number) as f64;
+		let mut integral = 0.;
+		for j in 0..number {
+			let center = xi + (2 * j + 1) as f64 * half;
+			for (node, weight) in &rule {
+				let x = center + half * node;
+				integral += weight * match function(x) {
+					Ok(f) => f,
+					Err(message) => return Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+				};
+			}
+		}
+		Ok(integral * half)
+	};
+	let mut number = 1;
+	let mut integral = match composite(number) {
+		Ok(integral) => integral,
+		Err(message) => return Err(message),
+	};
+	let mut evaluations = settings.points;
+	let mut difference = f64::INFINITY;
+	let mut converged = false;
+	let max_subdivisions = settings.max_subdivisions(MAX_GAUSS_LEGENDRE, LIMIT_GAUSS_LEGENDRE);
+	while !converged && number <= max_subdivisions / 2 {
+		number *= 2;
+		let integral_new = match composite(number) {
+			Ok(integral) => integral,
+			Err(message) => return Err(message),
+		};
+		evaluations += number * settings.points;
+		difference = (integral_new - integral).abs();
+		integral = integral_new;
+		converged = difference <= settings.tolerance(integral);
+	}
+	Ok(Estimate {
+		integral,
+		error: difference,
+		subdivisions: number,
+		evaluations,
+		method: "gauss-legendre".to_string(),
+		converged,
+		history: vec![],
+	})
+}
+
+// Integrates a function over a finite range by the tanh-sinh (double-exponential) rule, which
+// never evaluates the function at either endpoint and is therefore suited to integrable
+// singularities there.  The substitution x = c + d tanh(pi sinh(t) / 2) makes the integrand
+// decay so rapidly in t that the trapezoidal rule in t converges very rapidly as its step halves.
+pub fn tanh_sinh(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings) -> Result<Estimate, String> {
+	let d = (xf - xi) / 2.;
+	let half_pi = std::f64::consts::PI / 2.;
+	// the term in the sum for a particular value of t
+	let term = |t: f64| -> Result<f64, String> {
+		let u = half_pi * t.sinh();
+		// distance from the nearer endpoint, calculated so as to avoid cancellation
+		let delta = 2. * d / ((2. * u.abs()).exp() + 1.);
+		let x = if t > 0. {xf - delta} else {xi + delta};
+		let weight = d * half_pi * t.cosh() / u.cosh().powi(2);
+		// Points which are indistinguishable from an endpoint contribute negligibly.
+		if delta == 0. || weight == 0. || x == xi || x == xf {
+			return Ok(0.);
+		}
+		match function(x) {
+			Ok(f) => Ok(f * weight),
+			Err(message) => Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+		}
+	};
+	let mut h = 1.;
+	let mut sum = match term(0.) {
+		Ok(sum) => sum,
+		Err(message) => return Err(message),
+	};
+	let mut evaluations = 1;
+	let mut k = 1;
+	while k as f64 * h <= T_MAX {
+		for t in &[k as f64 * h, -(k as f64) * h] {
+			sum += match term(*t) {
+				Ok(term) => term,
+				Err(message) => return Err(message),
+			};
+		}
+		evaluations += 2;
+		k += 1;
+	}
+	let mut integral = h * sum;
+	let mut difference = f64::INFINITY;
+	let mut error = f64::INFINITY;
+	let max_subdivisions = settings.max_subdivisions(MAX_TANH_SINH, LIMIT_TANH_SINH) as f64;
+	let subdivisions = |h: f64| (2. * T_MAX / h).round() as i32;
+	let mut converged = false;
+	let mut stalled = false;
+	while !converged && !stalled && (subdivisions(h / 2.) as f64) <= max_subdivisions {
+		h /= 2.;
+		// Only the odd multiples of the new step are new points.
+		let mut k = 1;
+		while k as f64 * h <= T_MAX {
+			for t in &[k as f64 * h, -(k as f64) * h] {
+				sum += match term(*t) {
+					Ok(term) => term,
+					Err(message) => return Err(message),
+				};
+			}
+			evaluations += 2;
+			k += 2;
+		}
+		let integral_new = h * sum;
+		let difference_new = integral_new - integral;
+		// Near an endpoint which is not 0, roundoff in x limits the accuracy, and successive
+		// values then fluctuate (changing sign without shrinking) rather than converging.  The error
+		// is then only known to be within the bound on the fluctuation, which exceeds the tolerance.
+		let tolerance = settings.tolerance(integral_new);
+		let bound = tolerance.sqrt() * integral_new.abs().max(1.);
+		let fluctuating = difference_new * difference < 0. && difference_new.abs() > difference.abs() / 2. && difference_new.abs() <= bound;
+		converged = difference_new.abs() <= tolerance;
+		stalled = !converged && fluctuating;
+		error = if stalled {bound} else {difference_new.abs()};
+		integral = integral_new;
+		difference = difference_new;
+	}
+	Ok(Estimate {
+		integral,
+		error,
+		subdivisions: subdivisions(h),
+		evaluations,
+		method: "tanh-sinh".to_string(),
+		converged,
+		history: vec![],
 	})
 }
+
+// Applies a Kronrod rule and its embedded Gauss rule to one interval, returning the Kronrod
+// value, an estimate of its error which is scaled as in QUADPACK, and the roundoff error.
+fn kronrod(function: &dyn Fn(f64) -> Result<f64, String>, a: f64, b: f64, nodes: &[f64], kronrod_weights: &[f64], gauss_weights: &[f64]) -> Result<(f64, f64, f64), String> {
+	let center = (a + b) / 2.;
+	let half = (b - a) / 2.;
+	let mut kronrod = 0.;
+	let mut gauss = 0.;
+	let mut values = vec![];
+	for (i, node) in nodes.iter().enumerate() {
+		let xs = if *node == 0. {vec![center]} else {vec![center - half * node, center + half * node]};
+		for x in xs {
+			let f = match function(x) {
+				Ok(f) => f,
+				Err(message) => return Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+			};
+			kronrod += kronrod_weights[i] * f;
+			// The Gauss nodes are the odd-numbered ones.
+			if i % 2 == 1 {
+				gauss += gauss_weights[i / 2] * f;
+			}
+			values.push((i, f));
+		}
+	}
+	// The spread of the function about its mean scales the raw difference between the rules.
+	let mean = kronrod / 2.;
+	let spread = values.iter().fold(0., |sum, (i, f)| sum + kronrod_weights[*i] * (f - mean).abs()) * half.abs();
+	let absolute = values.iter().fold(0., |sum, (i, f)| sum + kronrod_weights[*i] * f.abs()) * half.abs();
+	let mut error = ((kronrod - gauss) * half).abs();
+	if spread != 0. && error != 0. {
+		error = spread * (200. * error / spread).powf(1.5).min(1.);
+	}
+	let roundoff = 50. * f64::EPSILON * absolute;
+	Ok((kronrod * half, error.max(roundoff), roundoff))
+}
+
+// an interval of the Gauss-Kronrod rules: its endpoints, integral, error, and roundoff error
+type Interval = (f64, f64, f64, f64, f64);
+
+// Globally adaptive Gauss-Kronrod integration (G7K15 or G10K21), which repeatedly bisects the
+// interval with the largest estimated error.  No function is evaluated at an endpoint.
+pub fn gauss_kronrod(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings, points: i32) -> Result<Estimate, String> {
+	match adaptive(function, &[xi, xf], settings, points, settings.max_subdivisions(MAX_INTERVALS, LIMIT_INTERVALS)) {
+		Ok((estimate, _)) => Ok(estimate),
+		Err(message) => Err(message),
+	}
+}
+
+// the adaptive Gauss-Kronrod rule, starting from the intervals between successive breakpoints,
+// which also returns the final intervals
+fn adaptive(
+	function: &dyn Fn(f64) -> Result<f64, String>,
+	breakpoints: &[f64],
+	settings: &Settings,
+	points: i32,
+	max_subdivisions: i32,
+) -> Result<(Estimate, Vec<Interval>), String> {
+	let (nodes, kronrod_weights, gauss_weights) = if points == 15 {
+		(&XK15[..], &WK15[..], &WG7[..])
+	} else {
+		(&XK21[..], &WK21[..], &WG10[..])
+	};
+	// Every application of the rule evaluates the function at each node, and a bisected interval's
+	// evaluations still count.
+	let per_interval = (2 * nodes.len() - 1) as i32;
+	let mut evaluations = 0;
+	let mut intervals: Vec<Interval> = vec![];
+	for pair in breakpoints.windows(2) {
+		match kronrod(function, pair[0], pair[1], nodes, kronrod_weights, gauss_weights) {
+			Ok((integral, error, roundoff)) => intervals.push((pair[0], pair[1], integral, error, roundoff)),
+			Err(message) => return Err(message),
+		};
+		evaluations += per_interval;
+	}
+	loop {
+		let integral = intervals.iter().fold(0., |sum, interval| sum + interval.2);
+		let error = intervals.iter().fold(0., |sum, interval| sum + interval.3);
+		let mut worst = 0;
+		for (i, interval) in intervals.iter().enumerate() {
+			if interval.3 > intervals[worst].3 {
+				worst = i;
+			}
+		}
+		let converged = error <= settings.tolerance(integral);
+		// Roundoff limits the accuracy regardless of the tolerance, and bisection cannot reduce it.
+		let stalled = intervals[worst].3 <= intervals[worst].4;
+		if converged || stalled || intervals.len() >= max_subdivisions as usize {
+			let estimate = Estimate {
+				integral,
+				error,
+				subdivisions: intervals.len() as i32,
+				evaluations,
+				method: format!("gk{}", points),
+				converged,
+				history: vec![],
+			};
+			return Ok((estimate, intervals));
+		}
+		let (a, b, _, _, _) = intervals.remove(worst);
+		let c = (a + b) / 2.;
+		for (a, b) in &[(a, c), (c, b)] {
+			match kronrod(function, *a, *b, nodes, kronrod_weights, gauss_weights) {
+				Ok((integral, error, roundoff)) => intervals.push((*a, *b, integral, error, roundoff)),
+				Err(message) => return Err(message),
+			};
+			evaluations += per_interval;
+		}
+	}
+}
+
+// the integral and error of each interval of a cumulative table, the number of evaluations, and
+// whether every interval converged
+type Cells = (Vec<(f64, f64)>, i32, bool);
+
+// Simpson's rule on a grid which includes the n + 1 breakpoints from xi to xf, for which the number
+// of subdivisions of each of the n intervals doubles until the whole range converges
+fn simpson_intervals(
+	function: &dyn Fn(f64) -> Result<f64, String>,
+	xi: f64,
+	xf: f64,
+	n: i32,
+	settings: &Settings,
+) -> Result<Cells, String> {
+	let evaluate = |x: f64| -> Result<f64, String> {
+		match function(x) {
+			Ok(f) => Ok(f),
+			Err(message) => Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+		}
+	};
+	let width = (xf - xi) / n as f64;
+	let mut fs = vec![];
+	for i in 0..=n {
+		fs.push(match evaluate(xi + i as f64 * width) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		});
+	}
+	let max_subdivisions = settings.max_subdivisions(MAX_SIMPSON, LIMIT_SIMPSON);
+	let mut number = 1; // subdivisions of each interval
+	let mut cells: Vec<(f64, f64)> = vec![];
+	loop {
+		number *= 2;
+		let h = width / number as f64;
+		// Only the odd-numbered points are new.
+		let mut new_fs = vec![];
+		for (j, f) in fs.iter().enumerate() {
+			new_fs.push(*f);
+			if j < fs.len() - 1 {
+				new_fs.push(match evaluate(xi + (2 * j + 1) as f64 * h) {
+					Ok(f) => f,
+					Err(message) => return Err(message),
+				});
+			}
+		}
+		fs = new_fs;
+		let mut new_cells = vec![];
+		for i in 0..n as usize {
+			let first = i * number as usize;
+			let mut sum = fs[first] + fs[first + number as usize];
+			for j in 1..number as usize {
+				sum += if j % 2 == 1 {4.} else {2.} * fs[first + j];
+			}
+			let integral = sum * h / 3.;
+			// The first level has nothing with which to compare.
+			let error = if cells.is_empty() {f64::INFINITY} else {(integral - cells[i].0).abs()};
+			new_cells.push((integral, error));
+		}
+		cells = new_cells;
+		let integral = cells.iter().fold(0., |sum, cell| sum + cell.0);
+		let error = cells.iter().fold(0., |sum, cell| sum + cell.1);
+		let converged = n * number >= MIN_SUBDIVISIONS && error <= settings.tolerance(integral);
+		if converged || n * number > max_subdivisions / 2 {
+			return Ok((cells, fs.len() as i32, converged));
+		}
+	}
+}
+
+// Filon's rule for the integral of f(x) sin(omega x) or f(x) cos(omega x) over a finite range,
+// which integrates exactly the product of the weight and a parabola through each successive pair
+// of intervals of f, so that the interval need not be small compared to the period of the weight.
+// The number of subdivisions doubles until successive values agree to within the tolerance.
+pub fn filon(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, omega: f64, sine: bool, settings: &Settings) -> Result<Estimate, String> {
+	let evaluate = |x: f64| -> Result<f64, String> {
+		match function(x) {
+			Ok(f) => Ok(f),
+			Err(message) => Err(format!("Cannot evaluate function at x: {}{}", x, message)),
+		}
+	};
+	let weight = |x: f64| if sine {(omega * x).sin()} else {(omega * x).cos()};
+	// the antiderivative of the weight, apart from a factor of 1 / omega
+	let antiderivative = |x: f64| if sine {-(omega * x).cos()} else {(omega * x).sin()};
+	let mut fs = vec![];
+	for x in &[xi, xf] {
+		fs.push(match evaluate(*x) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		});
+	}
+	let max_subdivisions = settings.max_subdivisions(MAX_SIMPSON, LIMIT_SIMPSON);
+	let mut number = 1;
+	let mut integral = f64::INFINITY;
+	let mut difference = f64::INFINITY;
+	let mut converged = false;
+	while !converged && number <= max_subdivisions / 2 {
+		number *= 2;
+		let h = (xf - xi) / number as f64;
+		// Only the odd-numbered points are new.
+		let mut new_fs = vec![];
+		for (j, f) in fs.iter().enumerate() {
+			new_fs.push(*f);
+			if j < fs.len() - 1 {
+				new_fs.push(match evaluate(xi + (2 * j + 1) as f64 * h) {
+					Ok(f) => f,
+					Err(message) => return Err(message),
+				});
+			}
+		}
+		fs = new_fs;
+		let theta = omega * h;
+		// For small theta the series avoid the cancellation in the closed forms.
+		let (alpha, beta, gamma) = if theta.abs() < 1. / 6. {
+			let t2 = theta * theta;
+			(
+				theta * t2 * (2. / 45. - t2 * (2. / 315. - t2 * 2. / 4725.)),
+				2. / 3. + t2 * (2. / 15. - t2 * (4. / 105. - t2 * 2. / 567.)),
+				4. / 3. - t2 * (2. / 15. - t2 * (1. / 210. - t2 / 11340.)),
+			)
+		} else {
+			let (sin, cos) = (theta.sin(), theta.cos());
+			let t3 = theta.powi(3);
+			(
+				(theta * theta + theta * sin * cos - 2. * sin * sin) / t3,
+				2. * (theta * (1. + cos * cos) - 2. * sin * cos) / t3,
+				4. * (sin - theta * cos) / t3,
+			)
+		};
+		let mut even = -(fs[0] * weight(xi) + fs[number as usize] * weight(xf)) / 2.;
+		let mut odd = 0.;
+		for (j, f) in fs.iter().enumerate() {
+			let term = f * weight(xi + j as f64 * h);
+			if j % 2 == 0 {even += term} else {odd += term}
+		}
+		let boundary = fs[number as usize] * antiderivative(xf) - fs[0] * antiderivative(xi);
+		let integral_new = h * (alpha * boundary + beta * even + gamma * odd);
+		difference = (integral_new - integral).abs();
+		integral = integral_new;
+		converged = number >= MIN_SUBDIVISIONS && difference <= settings.tolerance(integral);
+	}
+	Ok(Estimate {
+		integral,
+		error: difference,
+		subdivisions: number,
+		evaluations: number + 1,
+		method: "filon".to_string(),
+		converged,
+		history: vec![],
+	})
+}
+
+// The rules which evaluate the function at the endpoints are replaced by the tanh-sinh rule if
+// the function cannot be evaluated there.
+pub fn quadrature(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings) -> Result<Estimate, String> {
+	let method = settings.method.as_str();
+	match method {
+		"gauss-legendre" => return gauss_legendre(function, xi, xf, settings),
+		"gk15" => return gauss_kronrod(function, xi, xf, settings, 15),
+		"gk21" => return gauss_kronrod(function, xi, xf, settings, 21),
+		_ => (),
+	}
+	if method == "tanh-sinh" {
+		return tanh_sinh(function, xi, xf, settings);
+	}
+	if function(xi).is_err() || function(xf).is_err() {
+		if !settings.fallback {
+			return Err(format!("The {} rule cannot be used, because the function cannot be evaluated at an endpoint.", method));
+		}
+		return tanh_sinh(function, xi, xf, settings);
+	}
+	match method {
+		"trapezoid" => trapezoid(function, xi, xf, settings, false),
+		"romberg" => trapezoid(function, xi, xf, settings, true),
+		_ => simpson(function, xi, xf, settings),
+	}
+}
+
+// An infinite range is mapped onto a finite one, for which the integrand at the image of an
+// infinite limit is calculated as a limit.  Returns the integral and the number of subdivisions.
+pub fn integrate(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, settings: &Settings) -> Result<Estimate, String> {
+	if xi == xf {
+		if xi.is_finite() {
+			return Ok(Estimate {integral: 0., error: 0., subdivisions: 0, evaluations: 0, method: settings.method.clone(), converged: true, history: vec![]});
+		}
+		return Err(format!("The range of integration from {} to {} is ill-defined.", xi, xf));
+	}
+	if xi > xf {
+		return match integrate(function, xf, xi, settings) {
+			Ok(estimate) => Ok(estimate.negated()),
+			Err(message) => Err(message),
+		};
+	}
+	if xi.is_finite() && xf.is_finite() {
+		return quadrature(function, xi, xf, settings);
+	}
+	// x and dx/dt as functions of t, which ranges from -1 to 1 or from 0 to 1
+	type Transform = Box<dyn Fn(f64) -> (f64, f64)>;
+	let (ti, transform): (f64, Transform) = if xi.is_finite() {
+		(0., Box::new(move |t: f64| (xi + t / (1. - t), 1. / (1. - t).powi(2))))
+	} else if xf.is_finite() {
+		(0., Box::new(move |t: f64| (xf - t / (1. - t), 1. / (1. - t).powi(2))))
+	} else {
+		(-1., Box::new(|t: f64| (t / (1. - t * t), (1. + t * t) / (1. - t * t).powi(2))))
+	};
+	let integrand = |t: f64| -> Result<f64, String> {
+		let (x, dxdt) = transform(t);
+		match function(x) {
+			Ok(f) => Ok(f * dxdt),
+			Err(message) => Err(message),
+		}
+	};
+	let transformed = |t: f64| -> Result<f64, String> {
+		if t.abs() != 1. {
+			return integrand(t);
+		}
+		// Extrapolation of the integrand (with an error series in 1 - |t|) is a derivative of order 0.
+		let estimate = |h: f64| -> Result<(f64, f64), String> {
+			match integrand(t * (1. - h)) {
+				Ok(f) => Ok((f, f64::EPSILON * f.abs())),
+				Err(message) => Err(message),
+			}
+		};
+		match differentiation::differentiate(&estimate, 0.1, 1) {
+			Ok((value, _)) => Ok(value),
+			Err(message) => Err(format!("Cannot evaluate the integrand at infinity: {}", message)),
+		}
+	};
+	quadrature(&transformed, ti, 1., settings)
+}
+
+// the largest cap on the number of subdivisions which may be requested for a method
+fn limit(method: &str) -> i32 {
+	match method {
+		"gauss-legendre" => LIMIT_GAUSS_LEGENDRE,
+		"tanh-sinh" => LIMIT_TANH_SINH,
+		"gk15" | "gk21" => LIMIT_INTERVALS,
+		_ => LIMIT_SIMPSON,
+	}
+}
+
+// the optional settings which are common to this page and to others which integrate, as they
+// appear in the query string of a url
+#[derive(FromForm, Clone, Copy, Default)]
+pub struct Query<'r> {
+	pub method: Option<&'r RawStr>,
+	pub epsilon: Option<&'r RawStr>,
+	pub rel_epsilon: Option<&'r RawStr>,
+	pub max_subdivisions: Option<&'r RawStr>,
+	pub points: Option<&'r RawStr>,
+}
+
+// Parses the optional settings which are common to this page and to others which integrate.
+pub fn settings(query: &Query, default_method: &str) -> Result<Settings, String> {
+	let method = match query.method {
+		None => default_method.to_string(),
+		Some(method_str) => {
+			let method = method_str.to_lowercase();
+			if !METHODS.contains(&method.as_str()) {
+				return Err(format!("{} is not a method.  Use one of the following: {}.", method_str, METHODS.join(", ")));
+			}
+			method
+		},
+	};
+	let mut tolerances = vec![];
+	for (tolerance_str, default) in &[(query.epsilon, EPSILON), (query.rel_epsilon, 0.)] {
+		tolerances.push(match tolerance_str {
+			None => *default,
+			Some(tolerance_str) => match helper::parse_expression(tolerance_str.to_string()) {
+				Ok(tolerance) => {
+					if tolerance < 0. {
+						return Err(format!("The tolerance ({}) may not be negative.", tolerance));
+					}
+					tolerance
+				},
+				Err(message) => return Err(message),
+			},
+		});
+	}
+	let (epsilon, rel_epsilon) = (tolerances[0], tolerances[1]);
+	if epsilon == 0. && rel_epsilon == 0. {
+		return Err("The absolute and relative tolerances may not both be 0.".to_string());
+	}
+	let max_subdivisions = match query.max_subdivisions {
+		None => None,
+		Some(max_subdivisions_str) => match helper::parse_expression(max_subdivisions_str.to_string()) {
+			Ok(max_subdivisions) => {
+				if max_subdivisions.round() != max_subdivisions {
+					return Err(format!("{} is not an integer.", max_subdivisions));
+				} else if max_subdivisions < MIN_SUBDIVISIONS as f64 || max_subdivisions > limit(&method) as f64 {
+					return Err(format!("The maximum number of subdivisions for the method {} must be between {} and {}.", method, MIN_SUBDIVISIONS, limit(&method)));
+				}
+				Some(max_subdivisions as i32)
+			},
+			Err(message) => return Err(message),
+		},
+	};
+	let points = match query.points {
+		None => POINTS,
+		Some(points_str) => match helper::parse_expression(points_str.to_string()) {
+			Ok(points) => {
+				if points.round() != points {
+					return Err(format!("{} is not an integer.", points));
+				} else if points < 1. || points > MAX_POINTS as f64 {
+					return Err(format!("The number of points must be between 1 and {}.", MAX_POINTS));
+				}
+				points as i32
+			},
+			Err(message) => return Err(message),
+		},
+	};
+	Ok(Settings {epsilon, rel_epsilon, max_subdivisions, method, points, fallback: true})
+}
+
+// The Cauchy principal value of an integral over a range which contains simple poles.  Within a
+// symmetric interval about each pole the integrand is folded to f(c + u) + f(c - u), in which the
+// singularities cancel, and the rest of the range is integrated normally.
+pub fn principal_value(function: &dyn Fn(f64) -> Result<f64, String>, xi: f64, xf: f64, poles: &[f64], settings: &Settings) -> Result<Estimate, String> {
+	if xi > xf {
+		return match principal_value(function, xf, xi, poles, settings) {
+			Ok(estimate) => Ok(estimate.negated()),
+			Err(message) => Err(message),
+		};
+	}
+	if let Some(pole) = poles.iter().find(|pole| !pole.is_finite()) {
+		return Err(format!("The singular point {} must be finite.", pole));
+	}
+	let mut poles = poles.to_vec();
+	poles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	for (i, pole) in poles.iter().enumerate() {
+		if *pole <= xi || *pole >= xf {
+			return Err(format!("The singular point {} must lie strictly between the limits of integration.", pole));
+		}
+		if i > 0 && *pole == poles[i - 1] {
+			return Err(format!("The singular point {} is listed more than once.", pole));
+		}
+	}
+	// The pieces of the range are the symmetric intervals and the gaps between them.
+	let mut pieces = vec![];
+	let mut start = xi;
+	for (i, pole) in poles.iter().enumerate() {
+		let previous = if i == 0 {xi} else {poles[i - 1]};
+		let next = if i == poles.len() - 1 {xf} else {poles[i + 1]};
+		// half of the distance to the nearest (finite) neighbor, so that intervals cannot overlap
+		let distances: Vec<f64> = [pole - previous, next - pole].iter().cloned().filter(|d| d.is_finite()).collect();
+		let half_width = if distances.is_empty() {1.} else {distances.iter().cloned().fold(f64::INFINITY, f64::min) / 2.};
+		pieces.push((start, pole - half_width, None));
+		pieces.push((0., half_width, Some(*pole)));
+		start = pole + half_width;
+	}
+	pieces.push((start, xf, None));
+	let mut total = Estimate {integral: 0., error: 0., subdivisions: 0, evaluations: 0, method: "".to_string(), converged: true, history: vec![]};
+	let mut methods: Vec<String> = vec![];
+	for (a, b, pole) in pieces {
+		let estimate = match pole {
+			None => integrate(function, a, b, settings),
+			Some(c) => {
+				// The folded integrand is not evaluated at the pole, so that the tanh-sinh rule is used
+				// for it unless a Gauss-Kronrod rule was chosen.
+				let folded = |u: f64| -> Result<f64, String> {
+					if u == 0. {
+						return Err(format!("Cannot evaluate function at the singular point {}.", c));
+					}
+					// an offset which is exactly representable relative to the pole
+					let u = (c + u) - c;
+					if u == 0. {
+						return Ok(0.);
+					}
+					match (function(c + u), function(c - u)) {
+						(Ok(right), Ok(left)) => Ok(right + left),
+						(Err(message), _) | (_, Err(message)) => Err(message),
+					}
+				};
+				match integrate(&folded, a, b, settings) {
+					Ok(estimate) => Ok(Estimate {evaluations: 2 * estimate.evaluations, ..estimate}),
+					Err(message) => Err(message),
+				}
+			},
+		};
+		let estimate = match estimate {
+			Ok(estimate) => estimate,
+			Err(message) => return Err(message),
+		};
+		if !methods.contains(&estimate.method) {
+			methods.push(estimate.method.clone());
+		}
+		total = Estimate {
+			integral: total.integral + estimate.integral,
+			error: total.error + estimate.error,
+			subdivisions: total.subdivisions + estimate.subdivisions,
+			evaluations: total.evaluations + estimate.evaluations,
+			method: "".to_string(),
+			converged: total.converged && estimate.converged,
+			history: vec![],
+		};
+	}
+	Ok(Estimate {method: methods.join("+"), ..total})
+}
+
+pub fn raw(
+	xi_str: &RawStr,
+	xf_str: &RawStr,
+	input_str: &RawStr,
+	query: &Query,
+	pv_str: Option<&RawStr>,
+	history_str: Option<&RawStr>,
+) -> Result<Results, String> {
+	let xi = match helper::parse_extended(&xi_str.to_string()) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let xf = match helper::parse_extended(&xf_str.to_string()) {
+		Ok(xf) => xf,
+		Err(message) => return Err(message),
+	};
+	let settings = match settings(query, "simpson") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let pv = match pv_str {
+		None => None,
+		Some(pv_str) => match helper::parse_list(&pv_str.to_string()) {
+			Ok(poles) => Some(poles),
+			Err(message) => return Err(message),
+		},
+	};
+	let history = match history_str {
+		None => false,
+		Some(history_str) => match history_str.to_lowercase().as_str() {
+			"true" => true,
+			"false" => false,
+			_ => return Err(format!("The history ({}) must be either true or false.", history_str)),
+		},
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let estimate = match &pv {
+		None => integrate(&function, xi, xf, &settings),
+		Some(poles) => principal_value(&function, xi, xf, poles, &settings),
+	};
+	let estimate = match estimate {
+		Ok(estimate) => estimate,
+		Err(message) => return Err(message),
+	};
+	Ok(Results{
+		integral: estimate.integral,
+		error: estimate.error,
+		xi,
+		xf,
+		method: estimate.method,
+		subdivisions: estimate.subdivisions,
+		evaluations: estimate.evaluations,
+		epsilon: settings.epsilon,
+		rel_epsilon: settings.rel_epsilon,
+		max_subdivisions: settings.max_subdivisions,
+		converged: estimate.converged,
+		pv,
+		points: if settings.method == "gauss-legendre" {Some(settings.points)} else {None},
+		history: if history {Some(estimate.history)} else {None},
+	})
+}
+
+// the result of one of the rules in a comparison
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Rule {
+	pub method: String,
+	pub integral: f64,
+	pub error: f64,
+	pub subdivisions: i32,
+	pub evaluations: i32,
+	pub converged: bool,
+	pub message: Option<String>, // why the rule could not be used, in which case the numbers are meaningless
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Comparison {
+	pub xi: f64,
+	pub xf: f64,
+	pub epsilon: f64,
+	pub rel_epsilon: f64,
+	pub max_subdivisions: Option<i32>,
+	pub points: i32,
+	pub rules: Vec<Rule>,
+}
+
+// The same integral is calculated by each of several rules, with the same settings.
+pub fn compare(
+	xi_str: &RawStr,
+	xf_str: &RawStr,
+	input_str: &RawStr,
+	methods_str: Option<&RawStr>,
+	query: &Query,
+) -> Result<Comparison, String> {
+	let xi = match helper::parse_extended(&xi_str.to_string()) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let xf = match helper::parse_extended(&xf_str.to_string()) {
+		Ok(xf) => xf,
+		Err(message) => return Err(message),
+	};
+	let methods: Vec<String> = match methods_str {
+		None => METHODS.iter().map(|method| method.to_string()).collect(),
+		Some(methods_str) => methods_str.to_lowercase().split(',').map(|method| method.to_string()).collect(),
+	};
+	for method in &methods {
+		if !METHODS.contains(&method.as_str()) {
+			return Err(format!("{} is not a method.  Use one of the following: {}.", method, METHODS.join(", ")));
+		}
+	}
+	let settings = match settings(&Query {method: None, ..*query}, "simpson") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let mut rules = vec![];
+	// A rule which fails is reported as such, rather than being replaced by another.
+	for method in methods {
+		rules.push(match integrate(&function, xi, xf, &Settings {method: method.clone(), fallback: false, ..settings}) {
+			Ok(estimate) => Rule {
+				method: estimate.method,
+				integral: estimate.integral,
+				error: estimate.error,
+				subdivisions: estimate.subdivisions,
+				evaluations: estimate.evaluations,
+				converged: estimate.converged,
+				message: None,
+			},
+			Err(message) => Rule {
+				method,
+				integral: f64::NAN,
+				error: f64::NAN,
+				subdivisions: 0,
+				evaluations: 0,
+				converged: false,
+				message: Some(message),
+			},
+		});
+	}
+	Ok(Comparison {
+		xi,
+		xf,
+		epsilon: settings.epsilon,
+		rel_epsilon: settings.rel_epsilon,
+		max_subdivisions: settings.max_subdivisions,
+		points: settings.points,
+		rules,
+	})
+}
+
+// the integral of a smooth function times sin(omega x) or cos(omega x)
+pub fn oscillatory(
+	weight_str: &RawStr,
+	omega_str: &RawStr,
+	xi_str: &RawStr,
+	xf_str: &RawStr,
+	input_str: &RawStr,
+	query: &Query,
+) -> Result<Results, String> {
+	let sine = match weight_str.to_lowercase().as_str() {
+		"sin" => true,
+		"cos" => false,
+		_ => return Err(format!("{} is not a weight.  Use either sin or cos.", weight_str)),
+	};
+	let mut values = vec![];
+	for value_str in &[omega_str, xi_str, xf_str] {
+		values.push(match helper::parse_expression(value_str.to_string()) {
+			Ok(value) => value,
+			Err(message) => return Err(message),
+		});
+	}
+	let (omega, xi, xf) = (values[0], values[1], values[2]);
+	let settings = match settings(&Query {method: None, points: None, ..*query}, "filon") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let estimate = match filon(&function, xi, xf, omega, sine, &settings) {
+		Ok(estimate) => estimate,
+		Err(message) => return Err(message),
+	};
+	Ok(Results{
+		integral: estimate.integral,
+		error: estimate.error,
+		xi,
+		xf,
+		method: estimate.method,
+		subdivisions: estimate.subdivisions,
+		evaluations: estimate.evaluations,
+		epsilon: settings.epsilon,
+		rel_epsilon: settings.rel_epsilon,
+		max_subdivisions: settings.max_subdivisions,
+		converged: estimate.converged,
+		pv: None,
+		points: None,
+		history: None,
+	})
+}
+
+// the integral from the lower limit of a table to one of its points
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Row {
+	pub x: f64,
+	pub integral: f64,
+	pub error: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Table {
+	pub xi: f64,
+	pub xf: f64,
+	pub n: i32,
+	pub evaluations: i32,
+	pub converged: bool,
+	pub rows: Vec<Row>,
+}
+
+// The range is integrated once, on subdivisions which include the points of the grid, and the
+// integral over each interval of the grid is added to that over the preceding ones.
+pub fn table(
+	xi_str: &RawStr,
+	xf_str: &RawStr,
+	n_str: &RawStr,
+	input_str: &RawStr,
+	query: &Query,
+) -> Result<Table, String> {
+	let xi = match helper::parse_expression(xi_str.to_string()) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let xf = match helper::parse_expression(xf_str.to_string()) {
+		Ok(xf) => xf,
+		Err(message) => return Err(message),
+	};
+	let n = match helper::parse_expression(n_str.to_string()) {
+		Ok(n) => {
+			if n.round() != n {
+				return Err(format!("{} is not an integer.", n));
+			} else if n <= 0. || n > MAX_ROWS as f64 {
+				return Err(format!("The number of intervals must be between 1 and {}.", MAX_ROWS));
+			}
+			n as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let settings = match settings(&Query {points: None, ..*query}, "simpson") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let breakpoints: Vec<f64> = (0..=n).map(|i| xi + (i as f64) * (xf - xi) / (n as f64)).collect();
+	// the integral and error over each interval between successive breakpoints
+	let (cells, evaluations, converged) = if settings.method == "simpson" {
+		match simpson_intervals(&function, xi, xf, n, &settings) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		}
+	} else if settings.method == "gk15" || settings.method == "gk21" {
+		let points = if settings.method == "gk15" {15} else {21};
+		// The cap counts the intervals of the table as well as their bisections.
+		let max_subdivisions = settings.max_subdivisions(MAX_INTERVALS + n, LIMIT_INTERVALS + n);
+		let (estimate, intervals) = match adaptive(&function, &breakpoints, &settings, points, max_subdivisions) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		let mut cells = vec![(0., 0.); n as usize];
+		for (a, b, integral, error, _) in intervals {
+			let i = (((a + b) / 2. - xi) / (xf - xi) * n as f64).floor().max(0.).min((n - 1) as f64) as usize;
+			cells[i].0 += integral;
+			cells[i].1 += error;
+		}
+		(cells, estimate.evaluations, estimate.converged)
+	} else {
+		return Err(format!("A cumulative table requires simpson, gk15, or gk21, not {}.", settings.method));
+	};
+	let mut rows = vec![Row {x: xi, integral: 0., error: 0.}];
+	let (mut total, mut total_error) = (0., 0.);
+	for (i, (integral, error)) in cells.iter().enumerate() {
+		total += integral;
+		total_error += error;
+		rows.push(Row {x: breakpoints[i + 1], integral: total, error: total_error});
+	}
+	Ok(Table {xi, xf, n, evaluations, converged, rows})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn gauss_kronrod_example() {
+		let results = raw(
+			RawStr::from_str("-1"),
+			RawStr::from_str("1"),
+			RawStr::from_str("1d(1+10000x**2)"),
+			&Query {method: Some(RawStr::from_str("gk21")), ..Query::default()},
+			None, None,
+		).unwrap();
+		assert!((results.integral - 0.02 * 100f64.atan()).abs() < 1e-12);
+		assert!(results.converged);
+		// 13 bisections after the first application of the rule, each of which applies it twice
+		assert_eq!(results.subdivisions, 14);
+		assert_eq!(results.evaluations, 27 * 21);
+	}
+
+	#[test]
+	fn filon_example() {
+		let results = oscillatory(
+			RawStr::from_str("cos"),
+			RawStr::from_str("1000"),
+			RawStr::from_str("0"),
+			RawStr::from_str("1"),
+			RawStr::from_str("exp(x)"),
+			&Query::default(),
+		).unwrap();
+		// the real part of (e^(1 + 1000i) - 1)/(1 + 1000i)
+		let exact = (1f64.exp() * (1000f64.cos() + 1000. * 1000f64.sin()) - 1.) / (1. + 1e6);
+		assert!((results.integral - exact).abs() < 1e-10);
+		assert!((results.integral - 0.002248).abs() < 1e-6);
+		assert_eq!(results.method, "filon");
+	}
+}
@@ -0,0 +1,112 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::partial_differentiation::{self, Estimate};
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "JACOBIAN".to_string(),
+		links: calculus::links(9),
+		instructions: "This page calculates the Jacobian matrix of a vector of functions, each of which depends upon as many as four variables: <i>x</i>, <i>y</i>, <i>z</i>, and <i>w</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/jacobian</tt> type the following:<p align=center><tt>&sol;&lt;comma-separated coordinates of the point at which to calculate the matrix&gt;&sol;&lt;comma-separated functions of <i>x</I>, <i>y</i>, ...&gt;</tt></p>The number of coordinates determines the number of variables.  If the numbers of functions and of variables are equal, the matrix's determinant is also calculated.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the coordinates of the point)", helper::NOTE2),
+		example: "To calculate the Jacobian of the functions <i>x</i><sup>2</sup><i>y</i> and 5<i>x</i> + sin <i>y</i> at (<i>x</i>, <i>y</i>) = (1, 2), type <tt>/1,2/x**2y,5x+sin(y)</tt> after the current url address.  The rows of the matrix should be <tt>(4, 1)</tt> and <tt>(5, -0.41...)</tt>, and its determinant should be <tt>-6.66...</tt>".to_string(),
+		algorithm: "the same finite differences and extrapolation as on the partial differentiation page, followed by Gaussian elimination with partial pivoting for the determinant".to_string(),
+		json: "Type '/json' in the url bar immediately after 'jacobian' if you would like the result in this format rather than html.  A successful response will contain four properties: 'point' (an array of floats), 'values' (an array of the functions' values there), 'jacobian' (an array whose i-th element is an array for the i-th function, the j-th element of which is an object whose properties 'value' and 'error' are the function's derivative w.r.t. the j-th variable and its estimated absolute error), and 'determinant' (a float, or null if the matrix is not square).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub point: Vec<f64>,
+	pub values: Vec<f64>,
+	pub jacobian: Vec<Vec<Estimate>>,
+	pub determinant: Option<f64>,
+}
+
+// one of the functions of the point whose derivatives form a row of the matrix
+pub type Component<'a> = Box<dyn Fn(&[f64]) -> Result<f64, String> + 'a>;
+
+// The i-th row of the Jacobian is the gradient of the i-th function.
+pub fn jacobian(functions: &[Component], point: &[f64]) -> Result<Vec<Vec<Estimate>>, String> {
+	let mut matrix = vec![];
+	for function in functions {
+		let mut row = vec![];
+		for j in 0..point.len() {
+			let orders: Vec<i32> = (0..point.len()).map(|k| if k == j {1} else {0}).collect();
+			row.push(match partial_differentiation::partial(function.as_ref(), point, &orders) {
+				Ok(estimate) => estimate,
+				Err(message) => return Err(message),
+			});
+		}
+		matrix.push(row);
+	}
+	Ok(matrix)
+}
+
+// Gaussian elimination, with partial pivoting to limit roundoff
+pub fn determinant(mut matrix: Vec<Vec<f64>>) -> f64 {
+	let n = matrix.len();
+	let mut determinant = 1.;
+	for i in 0..n {
+		// Swap into row i the remaining row whose element in column i is largest.
+		let mut pivot = i;
+		for k in i + 1..n {
+			if matrix[k][i].abs() > matrix[pivot][i].abs() {
+				pivot = k;
+			}
+		}
+		if matrix[pivot][i] == 0. {
+			return 0.;
+		}
+		if pivot != i {
+			matrix.swap(i, pivot);
+			determinant = -determinant;
+		}
+		determinant *= matrix[i][i];
+		let pivot_row = matrix[i].clone();
+		for row in &mut matrix[i + 1..] {
+			let factor = row[i] / pivot_row[i];
+			for (element, above) in row[i..].iter_mut().zip(&pivot_row[i..]) {
+				*element -= factor * above;
+			}
+		}
+	}
+	determinant
+}
+
+pub fn raw(point_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let point = match helper::parse_list(&point_str.to_string()) {
+		Ok(point) => point,
+		Err(message) => return Err(message),
+	};
+	let n = point.len();
+	if n > helper::VARIABLES.len() {
+		return Err(format!("A function may have no more than {} variables.", helper::VARIABLES.len()));
+	}
+	let names = &helper::VARIABLES[..n];
+	let mut functions: Vec<Component> = vec![];
+	for expression in input_str.as_str().split(',') {
+		let expression = expression.to_string();
+		functions.push(Box::new(move |coords: &[f64]| helper::function(expression.clone(), names, coords)));
+	}
+	let mut values = vec![];
+	for function in &functions {
+		values.push(match function(&point) {
+			Ok(value) => value,
+			Err(message) => return Err(message),
+		});
+	}
+	let matrix = match jacobian(&functions, &point) {
+		Ok(matrix) => matrix,
+		Err(message) => return Err(message),
+	};
+	let square = if functions.len() == n {
+		Some(determinant(matrix.iter().map(|row| row.iter().map(|estimate| estimate.value).collect()).collect()))
+	} else {
+		None
+	};
+	Ok(Results {point, values, jacobian: matrix, determinant: square})
+}
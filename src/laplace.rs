@@ -0,0 +1,171 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integration;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "LAPLACE TRANSFORMS".to_string(),
+		links: calculus::links(18),
+		instructions: "This page calculates the Laplace transform <i>F</i>(<i>s</i>) = &int;<sub>0</sub><sup>&infin;</sup><i>e</i><sup>-<i>st</i></sup><i>f</i>(<i>t</i>) <i>dt</i> of a function of <i>t</i>, or the inverse transform <i>f</i>(<i>t</i>) of a function of <i>s</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/laplace</tt> type one of the following:<ul><li>for the transform: <tt>&sol;&lt;comma-separated list of values of <i>s</i>&gt;&sol;&lt;function of <i>t</i>&gt;</tt></li><li>for the inverse transform: <tt>&sol;inverse&sol;&lt;smallest value of <i>t</i>&gt;&sol;&lt;largest value of <i>t</i>&gt;&sol;&lt;number of intervals (no greater than 1000)&gt;&sol;&lt;function of <i>s</i>&gt;</tt></li></ul>The inverse transform is calculated at equally spaced values of <i>t</i>, each of which must be positive.  The settings <tt>method</tt>, <tt>epsilon</tt>, <tt>rel_epsilon</tt>, and <tt>max_subdivisions</tt> of the integration page may be appended to the url of the transform (but not that of the inverse transform), for which the default method is the 21-point Gauss-Kronrod rule.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the values of s and t)", helper::NOTE2),
+		example: "Typing <tt>/1,2/t*exp(-t)</tt> after the current url address should give the transform 1/(<i>s</i> + 1)<sup>2</sup> at <i>s</i> = 1 and 2: <tt>0.25</tt> and <tt>0.111...</tt>  Typing <tt>/2/exp(t)</tt> should give 1/(<i>s</i> - 1) = <tt>1</tt>, even though <i>e<sup>t</sup></i> itself overflows long before the integrand becomes negligible.  Typing <tt>/inverse/1/3/2/1d(s+1)</tt> should give approximately <i>e</i><sup>-<i>t</i></sup> at <i>t</i> = 1, 2, and 3: <tt>0.3679</tt>, <tt>0.1353</tt>, and <tt>0.0498</tt>.".to_string(),
+		algorithm: "the transform is calculated as an integral over an infinite range, as on the integration page.  The inverse transform is calculated by the Gaver-Stehfest formula <i>f</i>(<i>t</i>) &asymp; (ln 2/<i>t</i>)&Sigma;<i>V<sub>k</sub>F</i>(<i>k</i> ln 2/<i>t</i>), with 14 terms, which requires only real values of <i>s</i> (unlike Talbot's method, which requires complex ones).  Its error is estimated by comparison with the formula with 12 terms.  The formula works best for smooth functions which do not oscillate rapidly, and it is unreliable for discontinuous ones".to_string(),
+		json: "Type '/json' in the url bar immediately after 'laplace' (or, for the inverse transform, after 'inverse') if you would like the result in this format rather than html.  A successful response will contain four properties: 'inverse' (a boolean), 'method' (the rule used for the integrals, or 'stehfest' for the inverse transform), 'rows' (an array of objects, each with properties 'point' (the value of <i>s</i>, or of <i>t</i> for the inverse transform), 'value' (that of the transform or inverse transform), and 'error' (its estimated absolute error)), and 'converged' (false if any of the integrals reached the cap on its number of subdivisions before converging).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Row {
+	pub point: f64,
+	pub value: f64,
+	pub error: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub inverse: bool,
+	pub method: String,
+	pub rows: Vec<Row>,
+	pub converged: bool,
+}
+
+const STEHFEST: usize = 14; // number of terms, which must be even and is limited by roundoff
+const MAX_ROWS: i32 = 1000;
+
+fn factorial(n: usize) -> f64 {
+	(1..=n).fold(1., |product, i| product * i as f64)
+}
+
+// the Gaver-Stehfest weights V1, V2, ..., Vn
+fn weights(n: usize) -> Vec<f64> {
+	let half = n / 2;
+	(1..=n).map(|k| {
+		// j runs from k/2, rounded up, to the smaller of k and n/2.
+		let sum = (k - k / 2..=k.min(half)).fold(0., |sum, j| {
+			sum + (j as f64).powi(half as i32) * factorial(2 * j)
+				/ (factorial(half - j) * factorial(j) * factorial(j - 1) * factorial(k - j) * factorial(2 * j - k))
+		});
+		if (k + half) % 2 == 1 {-sum} else {sum}
+	}).collect()
+}
+
+// e^(-st) f, which is computed from logarithms wherever the exponential alone under- or overflows
+fn damped(f: f64, s: f64, t: f64) -> f64 {
+	let weight = (-s * t).exp();
+	if f == 0. {
+		0.
+	} else if weight > 0. && weight.is_finite() {
+		weight * f
+	} else if f.is_finite() {
+		(f.abs().ln() - s * t).exp() * f.signum()
+	} else if weight == 0. {
+		// Both have left the range of floats, and the transform can only exist if the exponential wins.
+		0.
+	} else {
+		weight * f
+	}
+}
+
+pub fn transform(s_str: &RawStr, input_str: &RawStr, query: &integration::Query) -> Result<Results, String> {
+	let s_list = match helper::parse_list(&s_str.to_string()) {
+		Ok(s_list) => s_list,
+		Err(message) => return Err(message),
+	};
+	let settings = match integration::settings(&integration::Query {points: None, ..*query}, "gk21") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let function = |t: f64| helper::function(input_str.to_string(), &["t"], &[t]);
+	let mut rows = vec![];
+	let mut converged = true;
+	for s in s_list {
+		let integrand = |t: f64| -> Result<f64, String> {
+			match function(t) {
+				Ok(f) => Ok(damped(f, s, t)),
+				Err(message) => Err(message),
+			}
+		};
+		let estimate = match integration::integrate(&integrand, 0., f64::INFINITY, &settings) {
+			Ok(estimate) => estimate,
+			Err(message) => return Err(format!("Error for s = {}: {}", s, message)),
+		};
+		if !estimate.integral.is_finite() {
+			return Err(format!("The integral for s = {} does not exist.", s));
+		}
+		converged = converged && estimate.converged;
+		rows.push(Row {point: s, value: estimate.integral, error: estimate.error});
+	}
+	Ok(Results {inverse: false, method: settings.method, rows, converged})
+}
+
+pub fn inverse(ti_str: &RawStr, tf_str: &RawStr, n_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let mut limits = vec![];
+	for t_str in &[ti_str, tf_str] {
+		limits.push(match helper::parse_expression(t_str.to_string()) {
+			Ok(t) => {
+				if !t.is_finite() || t <= 0. {
+					return Err(format!("The value t = {} must be positive and finite.", t));
+				}
+				t
+			},
+			Err(message) => return Err(message),
+		});
+	}
+	let (ti, tf) = (limits[0], limits[1]);
+	let n = match helper::parse_expression(n_str.to_string()) {
+		Ok(n) => {
+			if n.round() != n {
+				return Err(format!("{} is not an integer.", n));
+			} else if n <= 0. || n > MAX_ROWS as f64 {
+				return Err(format!("The number of intervals must be between 1 and {}.", MAX_ROWS));
+			}
+			n as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let function = |s: f64| helper::function(input_str.to_string(), &["s"], &[s]);
+	// The formula with fewer terms uses the first few of the same values of the function.
+	let (long, short) = (weights(STEHFEST), weights(STEHFEST - 2));
+	let ln2 = std::f64::consts::LN_2;
+	let mut rows = vec![];
+	for i in 0..=n {
+		let t = ti + (i as f64) * (tf - ti) / (n as f64);
+		let mut values = vec![];
+		for k in 1..=STEHFEST {
+			let s = k as f64 * ln2 / t;
+			values.push(match function(s) {
+				Ok(value) => value,
+				Err(message) => return Err(format!("Cannot evaluate the function at s = {}: {}", s, message)),
+			});
+		}
+		let sum = |weights: &[f64]| weights.iter().zip(&values).fold(0., |sum, (weight, value)| sum + weight * value) * ln2 / t;
+		let value = sum(&long);
+		rows.push(Row {point: t, value, error: (value - sum(&short)).abs()});
+	}
+	Ok(Results {inverse: true, method: "stehfest".to_string(), rows, converged: true})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn transform_of_growing_functions() {
+		let results = transform(RawStr::from_str("2"), RawStr::from_str("exp(t)"), &integration::Query::default()).unwrap();
+		assert!((results.rows[0].value - 1.).abs() < 1e-10);
+		let results = transform(RawStr::from_str("1"), RawStr::from_str("exp(0.5t)"), &integration::Query::default()).unwrap();
+		assert!((results.rows[0].value - 2.).abs() < 1e-10);
+	}
+
+	#[test]
+	fn inverse_example() {
+		let results = inverse(RawStr::from_str("1"), RawStr::from_str("3"), RawStr::from_str("2"), RawStr::from_str("1d(s+1)")).unwrap();
+		for row in &results.rows {
+			assert!((row.value - (-row.point).exp()).abs() < 1e-4);
+		}
+	}
+}
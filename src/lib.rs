@@ -2,7 +2,7 @@ pub const INSTRUCTIONS: &str = "WELCOME TO MY CALCULUS APP";
 
 const GITHUB: &str = "https://pknipp.github.io";
 const HEROKU: &str = "https://basic-calculus.herokuapp.com";
-const LINKS: [[&str; 4]; 8] = [
+const LINKS: [[&str; 4]; 19] = [
 	[GITHUB, "/math", "back to", " math APIs page"],
 	[HEROKU, "", "back to", " calculus page"],
 	[HEROKU, "/differentiation", "differentiation", ""],
@@ -11,6 +11,17 @@ const LINKS: [[&str; 4]; 8] = [
 	[HEROKU, "/max-finding", "max-finding", ""],
 	[HEROKU, "/ode", "1st order", " differential equations"],
 	[HEROKU, "/ode2", "2nd order", " differential equations"],
+	[HEROKU, "/partial-differentiation", "partial differentiation", ""],
+	[HEROKU, "/jacobian", "Jacobian", " matrices"],
+	[HEROKU, "/taylor", "Taylor", " polynomials"],
+	[HEROKU, "/limit", "limits", ""],
+	[HEROKU, "/implicit-differentiation", "implicit differentiation", ""],
+	[HEROKU, "/multiple-integration", "multiple integration", ""],
+	[HEROKU, "/geometry", "geometry", " (arc length, area, and volume)"],
+	[HEROKU, "/monte-carlo", "Monte Carlo", " integration"],
+	[HEROKU, "/tabulated", "tabulated", " data"],
+	[HEROKU, "/fourier", "Fourier", " series"],
+	[HEROKU, "/laplace", "Laplace", " transforms"],
 ];
 
 pub fn general_page() -> String {format!(
@@ -21,7 +32,7 @@ pub fn general_page() -> String {format!(
 
 pub fn links(n: i32) -> String {
 	let mut links = "".to_string();
-	for j in 0..8 {
+	for j in 0..LINKS.len() {
 		let i = j as usize;
 		if i != n as usize {
 			links = format!("{}
@@ -0,0 +1,164 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::differentiation;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "LIMITS".to_string(),
+		links: calculus::links(11),
+		instructions: "This page calculates the limit of a function as <i>x</i> approaches some value, which may be <tt>inf</tt> or <tt>-inf</tt>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/limit</tt> type the following:<p align=center><tt>&sol;&lt;value which <i>x</i> approaches&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>By default the limits from both sides of a finite value are calculated, and the limit exists if they agree.  To calculate only one of them, append <tt>?side=left</tt> or <tt>?side=right</tt> to the url.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, "", helper::NOTE2),
+		example: "To calculate the limit of the function sin(<i>x</i>)/<i>x</i> as <i>x</i> approaches 0, type <tt>/0/sin(x)dx</tt> after the current url address.  The result should be <tt>1</tt>.  Typing <tt>/inf/(1+1dx)**x</tt> should give <tt>2.718...</tt>, and typing <tt>/0/1dx</tt> should report that the function diverges to -&infin; from the left and to &infin; from the right.".to_string(),
+		algorithm: "Richardson extrapolation to <i>h</i> = 0 of the sequence of values <i>f</i>(<i>a</i> &plusmn; <i>h</i>) for decreasing values of <i>h</i> (starting from several values scaled to the magnitude of <i>a</i>), as on the differentiation page, with an estimated error which is at least the difference from the results for the neighbouring starting values.  For infinite <i>a</i>, <i>x</i> is replaced by &plusmn;1/<i>h</i>.  The extrapolation is repeated in the variable &radic;<i>h</i>, which is more appropriate near the edge of a function's domain, and the result with the smaller estimated error is kept, although if both results converge its error is at least their difference plus the other's error.  A limit is considered infinite if |<i>f</i>| grows steadily as <i>h</i> approaches 0".to_string(),
+		json: "Type '/json' in the url bar immediately after 'limit' if you would like the result in this format rather than html.  A successful response will contain four properties: 'a' (a string representing the value which x approaches), 'left' and 'right' (each either null or an object with properties 'value', 'error' (null if the limit is infinite), and 'behavior', the last of which is 'converges', 'diverges to inf', 'diverges to -inf', or 'does not converge'), and 'value' (the limit, or null if it does not exist).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Side {
+	pub value: f64,
+	pub error: f64,
+	pub behavior: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub a: String,
+	pub left: Option<Side>,
+	pub right: Option<Side>,
+	pub value: Option<f64>,
+}
+
+const TOLERANCE: f64 = 1e-6; // largest relative error for a convergent limit
+
+// the limit as x approaches a from one side (direction = 1 for the right and -1 for the left)
+fn side(function: &dyn Fn(f64) -> Result<f64, String>, a: f64, direction: f64) -> Result<Side, String> {
+	// x as a function of the distance h to the limit, which is never 0
+	let x = |h: f64| if a.is_finite() {a + direction * h} else {-direction / h};
+	// An infinite limit is signalled by a function whose magnitude grows steadily as h shrinks.
+	let mut fs = vec![];
+	for h in &[1e-4, 1e-8, 1e-12] {
+		match function(x(h * if a.is_finite() {a.abs().max(1.)} else {1.})) {
+			Ok(f) => fs.push(f),
+			Err(message) => return Err(message),
+		};
+	}
+	if fs[2].abs() > fs[1].abs() && fs[1].abs() > fs[0].abs() && fs[2].abs() > 2. * fs[0].abs() && fs[0] * fs[2] > 0. {
+		let behavior = if fs[2] > 0. {"diverges to inf"} else {"diverges to -inf"};
+		return Ok(Side {value: fs[2], error: f64::INFINITY, behavior: behavior.to_string()});
+	}
+	let h_max = if a.is_finite() {differentiation::step(a, 0)} else {0.1};
+	// Extrapolation of the function value (with an error series in h) is a derivative of order 0.
+	// Near the edge of a function's domain the series is often in the square root of h instead.
+	let mut results = vec![];
+	for root in &[1, 2] {
+		let estimate = |h: f64| -> Result<(f64, f64), String> {
+			match function(x(h.powi(*root))) {
+				Ok(f) => Ok((f, f64::EPSILON * f.abs())),
+				Err(message) => Err(message),
+			}
+		};
+		let extrapolations = differentiation::extrapolations(&estimate, h_max.powf(1. / *root as f64), 1);
+		match differentiation::least_uncertain(&extrapolations) {
+			Ok(i) => {
+				let (value, mut error) = extrapolations[i].clone().unwrap();
+				// A series in the wrong variable (eg, for x ln(x), whose series is in neither) gives
+				// results which drift with the starting step, so neighbouring ones must agree.
+				for j in &[i.wrapping_sub(1), i + 1] {
+					if let Some(Ok((other, _))) = extrapolations.get(*j) {
+						error = error.max((other - value).abs());
+					}
+				}
+				results.push((value, error));
+			},
+			Err(message) => if results.is_empty() && *root == 2 {
+				return Err(message);
+			},
+		}
+	}
+	let small = |(value, error): (f64, f64)| error <= TOLERANCE * value.abs().max(1.);
+	let (value, mut error) = results.iter().cloned().fold(results[0], |best, result| if result.1 < best.1 {result} else {best});
+	// Each error estimate alone may be too optimistic, so the two results must also agree unless
+	// one of them is not even steady.
+	for other in &results {
+		if small(*other) {
+			error = error.max((other.0 - value).abs() + other.1);
+		}
+	}
+	let converges = small((value, error));
+	let behavior = if converges {"converges"} else {"does not converge"};
+	Ok(Side {value, error, behavior: behavior.to_string()})
+}
+
+pub fn raw(a_str: &RawStr, input_str: &RawStr, side_str: Option<&RawStr>) -> Result<Results, String> {
+	let a = match helper::parse_extended(&a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	// An infinite value may be approached from only one side.
+	let (use_left, use_right) = match side_str {
+		None => (a != f64::NEG_INFINITY, a != f64::INFINITY),
+		Some(side_str) => match side_str.to_lowercase().as_str() {
+			"left" => (true, false),
+			"right" => (false, true),
+			_ => return Err(format!("{} is not a side.  Use either left or right.", side_str)),
+		},
+	};
+	if (use_left && a == f64::NEG_INFINITY) || (use_right && a == f64::INFINITY) {
+		return Err(format!("{} cannot be approached from that side.", a));
+	}
+	let function = |x| helper::function1(input_str.to_string(), x);
+	// A side is omitted if the function cannot be evaluated there, unless it alone was requested.
+	let mut sides = vec![];
+	for (used, direction) in &[(use_left, -1.), (use_right, 1.)] {
+		sides.push(if !used {None} else {
+			match side(&function, a, *direction) {
+				Ok(side) => Some(side),
+				Err(message) => if side_str.is_some() || !a.is_finite() {
+					return Err(message);
+				} else {
+					None
+				},
+			}
+		});
+	}
+	let right = sides.pop().unwrap();
+	let left = sides.pop().unwrap();
+	let converges = |side: &Side| side.behavior == "converges";
+	let value = match (&left, &right) {
+		(None, None) => return Err(format!("The function cannot be evaluated near x = {}.", a)),
+		(Some(left), Some(right)) => {
+			if converges(left) && converges(right) && !differentiation::disagree((left.value, left.error), (right.value, right.error)) {
+				Some((left.value + right.value) / 2.)
+			} else {
+				None
+			}
+		},
+		(Some(side), None) | (None, Some(side)) => if converges(side) {Some(side.value)} else {None},
+	};
+	Ok(Results {a: a.to_string(), left, right, value})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sinc_example() {
+		let results = raw(RawStr::from_str("0"), RawStr::from_str("sin(x)dx"), None).unwrap();
+		assert!((results.value.unwrap() - 1.).abs() < 1e-10);
+	}
+
+	#[test]
+	fn logarithmic_series() {
+		// x ln(x) has a series in neither h nor its square root, so neither extrapolation is steady.
+		let results = raw(RawStr::from_str("0"), RawStr::from_str("x*ln(x)"), Some(RawStr::from_str("right"))).unwrap();
+		let right = results.right.unwrap();
+		assert!(right.value.abs() <= right.error);
+		assert_eq!(right.behavior, "does not converge");
+	}
+}
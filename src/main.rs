@@ -1,7 +1,10 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 #[macro_use] extern crate rocket;
-use rocket::http::RawStr;
+use rocket::http::{RawStr, ContentType};
+use rocket::request::LenientForm;
 use rocket::response::content;
+use rocket::Data;
+use std::io::Read;
 
 mod helper;
 
@@ -11,6 +14,17 @@ mod root_finding;
 mod max_finding;
 mod ode;
 mod ode2;
+mod partial_differentiation;
+mod jacobian;
+mod taylor;
+mod limit;
+mod implicit_differentiation;
+mod multiple_integration;
+mod geometry;
+mod monte_carlo;
+mod tabulated;
+mod fourier;
+mod laplace;
 
 extern crate calculus;
 extern crate serde_json;
@@ -50,22 +64,132 @@ fn ode2_page() -> content::Html<String> {
   content::Html(ode2::page())
 }
 
-#[get("/differentiation/json/<x_str>/<input_str>")]
-fn differentiation_json(x_str: &RawStr, input_str: &RawStr) -> String {
-  match differentiation::raw(x_str, input_str) {
+#[get("/partial-differentiation")]
+fn partial_differentiation_page() -> content::Html<String> {
+  content::Html(partial_differentiation::page())
+}
+
+#[get("/jacobian")]
+fn jacobian_page() -> content::Html<String> {
+  content::Html(jacobian::page())
+}
+
+#[get("/differentiation/json/<x_str>/<input_str>?<order>&<method>")]
+fn differentiation_json(x_str: &RawStr, input_str: &RawStr, order: Option<&RawStr>, method: Option<&RawStr>) -> String {
+  match differentiation::raw(x_str, input_str, order, method) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/differentiation/table/json/<xmin_str>/<xmax_str>/<n_str>/<input_str>")]
+fn differentiation_table_json(
+  xmin_str: &RawStr,
+  xmax_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr
+) -> String {
+  match differentiation::table(xmin_str, xmax_str, n_str, input_str) {
+    Ok(table) => serde_json::to_string(&table).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/differentiation/table/csv/<xmin_str>/<xmax_str>/<n_str>/<input_str>")]
+fn differentiation_table_csv(
+  xmin_str: &RawStr,
+  xmax_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr
+) -> content::Content<String> {
+  let table = match differentiation::table(xmin_str, xmax_str, n_str, input_str) {
+    Ok(table) => table,
+    Err(message) => return content::Content(ContentType::CSV, message),
+  };
+  let mut csv = "x,f,f',f''\n".to_string();
+  for row in table.rows {
+    csv = format!("{}{},{},{},{}\n", csv, row.x, row.derivs[0], row.derivs[1], row.derivs[2]);
+  }
+  content::Content(ContentType::CSV, csv)
+}
+
+#[get("/integration/json/<xi_str>/<xf_str>/<input_str>?<pv>&<history>&<query..>")]
+fn integration_json(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  pv: Option<&RawStr>,
+  history: Option<&RawStr>,
+  query: LenientForm<integration::Query>,
+) -> String {
+  match integration::raw(xi_str, xf_str, input_str, &query, pv, history) {
     Ok(results) => serde_json::to_string(&results).unwrap(),
     Err(message) => format!("{{\"message\": {}}}", message),
   }
 }
 
-#[get("/integration/json/<xi_str>/<xf_str>/<input_str>")]
-fn integration_json(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> String {
-  match integration::raw(xi_str, xf_str, input_str) {
+#[get("/integration/compare/json/<xi_str>/<xf_str>/<input_str>?<methods>&<query..>")]
+fn integration_compare_json(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  methods: Option<&RawStr>,
+  query: LenientForm<integration::Query>,
+) -> String {
+  match integration::compare(xi_str, xf_str, input_str, methods, &query) {
+    Ok(comparison) => serde_json::to_string(&comparison).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/integration/oscillatory/json/<weight_str>/<omega_str>/<xi_str>/<xf_str>/<input_str>?<query..>")]
+fn integration_oscillatory_json(
+  weight_str: &RawStr,
+  omega_str: &RawStr,
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  query: LenientForm<integration::Query>,
+) -> String {
+  match integration::oscillatory(weight_str, omega_str, xi_str, xf_str, input_str, &query) {
     Ok(results) => serde_json::to_string(&results).unwrap(),
     Err(message) => format!("{{\"message\": {}}}", message),
   }
 }
 
+#[get("/integration/cumulative/json/<xi_str>/<xf_str>/<n_str>/<input_str>?<query..>")]
+fn integration_table_json(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  query: LenientForm<integration::Query>,
+) -> String {
+  match integration::table(xi_str, xf_str, n_str, input_str, &query) {
+    Ok(table) => serde_json::to_string(&table).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/integration/cumulative/csv/<xi_str>/<xf_str>/<n_str>/<input_str>?<query..>")]
+fn integration_table_csv(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  query: LenientForm<integration::Query>,
+) -> content::Content<String> {
+  let table = match integration::table(xi_str, xf_str, n_str, input_str, &query) {
+    Ok(table) => table,
+    Err(message) => return content::Content(ContentType::CSV, message),
+  };
+  let mut csv = "x,F,error\n".to_string();
+  for row in table.rows {
+    csv = format!("{}{},{},{}\n", csv, row.x, row.integral, row.error);
+  }
+  content::Content(ContentType::CSV, csv)
+}
+
 #[get("/root-finding/json/<x_str>/<input_str>")]
 fn root_finding_json(x_str: &RawStr, input_str: &RawStr) -> String {
   match root_finding::raw(x_str, input_str) {
@@ -109,10 +233,71 @@ fn ode2_json(
   }
 }
 
-#[get("/differentiation/<x_str>/<input_str>")]
-fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+#[get("/partial-differentiation/json/<point_str>/<input_str>?<wrt>")]
+fn partial_differentiation_json(point_str: &RawStr, input_str: &RawStr, wrt: Option<&RawStr>) -> String {
+  match partial_differentiation::raw(point_str, input_str, wrt) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/taylor")]
+fn taylor_page() -> content::Html<String> {
+  content::Html(taylor::page())
+}
+
+#[get("/limit")]
+fn limit_page() -> content::Html<String> {
+  content::Html(limit::page())
+}
+
+#[get("/implicit-differentiation")]
+fn implicit_differentiation_page() -> content::Html<String> {
+  content::Html(implicit_differentiation::page())
+}
+
+#[get("/multiple-integration")]
+fn multiple_integration_page() -> content::Html<String> {
+  content::Html(multiple_integration::page())
+}
+
+#[get("/geometry")]
+fn geometry_page() -> content::Html<String> {
+  content::Html(geometry::page())
+}
+
+#[get("/monte-carlo")]
+fn monte_carlo_page() -> content::Html<String> {
+  content::Html(monte_carlo::page())
+}
+
+#[get("/tabulated")]
+fn tabulated_page() -> content::Html<String> {
+  content::Html(tabulated::page())
+}
+
+#[get("/fourier")]
+fn fourier_page() -> content::Html<String> {
+  content::Html(fourier::page())
+}
+
+#[get("/laplace")]
+fn laplace_page() -> content::Html<String> {
+  content::Html(laplace::page())
+}
+
+#[get("/jacobian/json/<point_str>/<input_str>")]
+fn jacobian_json(point_str: &RawStr, input_str: &RawStr) -> String {
+  match jacobian::raw(point_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/differentiation/<x_str>/<input_str>?<order>&<method>")]
+fn differentiation_html(x_str: &RawStr, input_str: &RawStr, order: Option<&RawStr>, method: Option<&RawStr>) -> content::Html<String> {
   let instructions = differentiation::page();
-  let results = match differentiation::raw(x_str, input_str) {
+  let results = match differentiation::raw(x_str, input_str, order, method) {
     Ok(results) => results,
     Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the function f(x) = {}:<br>{}",
       instructions,
@@ -120,9 +305,16 @@ fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<Str
       message
     )),
   };
-  let text = if results.nonsingular {""} else {
+  let mut text = if results.nonsingular {""} else {
     "<br>(The function does not exist at that point,
     but these are the limits.)"
+  }.to_string();
+  match (&results.left, &results.right) {
+    (Some(_), None) => text += "<br>(Only the left side of the point is within the function's domain.)",
+    (None, Some(_)) => text += "<br>(Only the right side of the point is within the function's domain.)",
+    _ => if results.corner {
+      text += "<br>(The values from the left and right disagree, so the function has a jump, corner, or cusp there.)";
+    },
   };
   let mut expression = input_str.to_string();
   expression = str::replace(&expression, "%5E", "^");
@@ -130,37 +322,61 @@ fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<Str
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  let mut items = "".to_string();
+  for (order, deriv) in results.derivs.iter().enumerate() {
+    // primes for low orders, and a parenthesized superscript for the rest
+    let name = if order < 4 {"'".repeat(order)} else {format!("<sup>({})</sup>", order)};
+    let sides = match (results.corner, &results.left, &results.right, &results.left_errors, &results.right_errors) {
+      (true, Some(left), Some(right), Some(left_errors), Some(right_errors)) => format!(
+        " (left: {} &plusmn; {:.1e}, right: {} &plusmn; {:.1e})",
+        left[order],
+        left_errors[order],
+        right[order],
+        right_errors[order],
+      ),
+      _ => "".to_string(),
+    };
+    let value = if deriv.is_nan() {" does not exist".to_string()} else {
+      format!(" = {} &plusmn; {:.1e}", deriv, results.errors[order])
+    };
+    items = format!(
+      "{}<li>f{}{}{}</li>",
+      items,
+      name,
+      value,
+      sides,
+    );
+  }
+  let method = if results.method == "complex" {" (by the complex-step method)"} else {""};
   content::Html(format!(
-    "{}<br><br><b>results</b> at x = {} for the function f(x) =
-    {}:{}<ul><li>f = {}</li><li>f' = {}</li><li>f'' =
-    {}</li><li>f''' = {}</li></ul>",
+    "{}<br><br><b>results</b>{} at x = {} for the function f(x) =
+    {}:{}<ul>{}</ul>",
     instructions,
+    method,
     results.x,
     expression,
     text,
-    results.derivs[0],
-    results.derivs[1],
-    results.derivs[2],
-    results.derivs[3],
+    items,
   ))
 }
 
-#[get("/integration/<xi_str>/<xf_str>/<input_str>")]
-fn integration_html(
-  xi_str: &RawStr,
-  xf_str: &RawStr,
-  input_str: &RawStr,
+#[get("/differentiation/table/<xmin_str>/<xmax_str>/<n_str>/<input_str>")]
+fn differentiation_table_html(
+  xmin_str: &RawStr,
+  xmax_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr
 ) -> content::Html<String> {
-  let instructions = integration::page();
-  let results = match integration::raw(xi_str, xf_str, input_str) {
-    Ok(results) => results,
+  let instructions = differentiation::page();
+  let table = match differentiation::table(xmin_str, xmax_str, n_str, input_str) {
+    Ok(table) => table,
     Err(message) => return content::Html(format!(
-      "{}<br><br><b>result</b> for the integral from x = {} to
-      x = {} of the function f(x) = {}:<br>{}",
+      "{}<br><br><b>result</b> for tabulating the function f(x) = {}
+      from x = {} to x = {}:<br>{}",
       instructions,
-      xi_str,
-      xf_str,
       input_str,
+      xmin_str,
+      xmax_str,
       message
     )),
   };
@@ -170,17 +386,945 @@ fn integration_html(
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  let mut rows = "".to_string();
+  for row in &table.rows {
+    rows = format!(
+      "{}<div>{}</div><div>{}</div><div>{}</div><div>{}</div>",
+      rows,
+      row.x, row.derivs[0], row.derivs[1], row.derivs[2],
+    );
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat(4, 1fr); width:700px'>
+      <div>
+        <i>x</I>
+      </div>
+      <div>
+        <i>f</i>
+      </div>
+      <div>
+        <i>f'</i>
+      </div>
+      <div>
+        <i>f''</i>
+      </div>
+    </div>
+    <div style='
+      height:200px;
+      width:700px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat(4, 1fr);
+    '>
+      {}
+    </div></div>", rows);
+  rows = format!(
+    "<div style='display: flex;
+    justify-content: center;'>{}</div>",
+    rows,
+  );
   content::Html(format!(
-    "{}<br><br><b>result</b>: {} equals the definite integral from x = {}
-    to x = {} of the function f(x) = {}.<br>Convergence to an absolute
-    accuracy of {} required {} subdivisions.",
+    "{}<br><br><b>results</b> for the function f(x) = {}
+    from x = {} to x = {}:<br>{}",
     instructions,
-    results.integral,
-    results.xi,
-    results.xf,
-    str::replace(&expression, "X", "x"),
-    results.epsilon,
-    results.subdivisions,
+    expression,
+    table.xmin,
+    table.xmax,
+    rows,
+  ))
+}
+
+#[get("/partial-differentiation/<point_str>/<input_str>?<wrt>")]
+fn partial_differentiation_html(point_str: &RawStr, input_str: &RawStr, wrt: Option<&RawStr>) -> content::Html<String> {
+  let instructions = partial_differentiation::page();
+  let results = match partial_differentiation::raw(point_str, input_str, wrt) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the function f = {}:<br>{}",
+      instructions,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let names = &helper::VARIABLES[..results.point.len()];
+  let mut gradient = "".to_string();
+  for (i, estimate) in results.gradient.iter().enumerate() {
+    gradient = format!(
+      "{}<li>&part;f/&part;{} = {} &plusmn; {:.1e}</li>",
+      gradient,
+      names[i],
+      estimate.value,
+      estimate.error,
+    );
+  }
+  let mut hessian = "".to_string();
+  for row in &results.hessian {
+    let mut cells = "".to_string();
+    for estimate in row {
+      cells = format!("{}<td>{} &plusmn; {:.1e}</td>", cells, estimate.value, estimate.error);
+    }
+    hessian = format!("{}<tr>{}</tr>", hessian, cells);
+  }
+  let mixed = match results.partial {
+    None => "".to_string(),
+    Some(partial) => {
+      let mut denominator = "".to_string();
+      for name in names {
+        let n = partial.wrt.matches(name).count();
+        if n == 1 {
+          denominator = format!("{}&part;{}", denominator, name);
+        } else if n > 1 {
+          denominator = format!("{}&part;{}<sup>{}</sup>", denominator, name, n);
+        }
+      }
+      format!(
+        "<br>The mixed partial derivative &part;<sup>{}</sup>f/{} = {} &plusmn; {:.1e}",
+        partial.wrt.len(),
+        denominator,
+        partial.value,
+        partial.error,
+      )
+    },
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> at ({}) = ({}) for the function f({}) = {}:
+    <br>f = {}<br>The gradient's components are<ul>{}</ul>The Hessian matrix is
+    <table border=1>{}</table>{}",
+    instructions,
+    names.join(", "),
+    results.point.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "),
+    names.join(", "),
+    expression,
+    results.value,
+    gradient,
+    hessian,
+    mixed,
+  ))
+}
+
+#[get("/taylor/json/<x0_str>/<order_str>/<input_str>?<radius>")]
+fn taylor_json(x0_str: &RawStr, order_str: &RawStr, input_str: &RawStr, radius: Option<&RawStr>) -> String {
+  match taylor::raw(x0_str, order_str, input_str, radius) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/taylor/<x0_str>/<order_str>/<input_str>?<radius>")]
+fn taylor_html(x0_str: &RawStr, order_str: &RawStr, input_str: &RawStr, radius: Option<&RawStr>) -> content::Html<String> {
+  let instructions = taylor::page();
+  let results = match taylor::raw(x0_str, order_str, input_str, radius) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the function f(x) = {}:<br>{}",
+      instructions,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut items = "".to_string();
+  for (k, coefficient) in results.coefficients.iter().enumerate() {
+    items = format!("{}<li>c<sub>{}</sub> = {} &plusmn; {:.1e}</li>", items, k, coefficient, results.errors[k]);
+  }
+  let remainder = match (results.radius, results.remainder) {
+    (Some(radius), Some(remainder)) => format!(
+      "<br>Within {} of x = {}, the polynomial differs from the function by no more than about {}.",
+      radius, results.x0, remainder,
+    ),
+    _ => "".to_string(),
+  };
+  // The function and the polynomial are compared within the radius, which by default is 1.
+  let radius = results.radius.unwrap_or(1.);
+  let n = 20;
+  let mut rows = "".to_string();
+  for i in 0..=n {
+    let x = results.x0 - radius + 2. * radius * (i as f64) / (n as f64);
+    let p = results.evaluate(x);
+    let (f, difference) = match helper::function1(input_str.to_string(), x) {
+      Ok(f) => (f.to_string(), (f - p).to_string()),
+      Err(_) => ("undefined".to_string(), "".to_string()),
+    };
+    rows = format!("{}<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", rows, x, f, p, difference);
+  }
+  content::Html(format!(
+    "{}<br><br><b>results</b> for the Taylor polynomial of order {} about x = {} for the function f(x) = {}:
+    <br>The coefficients of the successive powers of x - x<sub>0</sub> are<ul>{}</ul>P(x) = {}{}
+    <table border=1><tr><th>x</th><th>f(x)</th><th>P(x)</th><th>f(x) - P(x)</th></tr>{}</table>",
+    instructions,
+    results.order,
+    results.x0,
+    expression,
+    items,
+    results.polynomial,
+    remainder,
+    rows,
+  ))
+}
+
+#[get("/limit/json/<a_str>/<input_str>?<side>")]
+fn limit_json(a_str: &RawStr, input_str: &RawStr, side: Option<&RawStr>) -> String {
+  match limit::raw(a_str, input_str, side) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/limit/<a_str>/<input_str>?<side>")]
+fn limit_html(a_str: &RawStr, input_str: &RawStr, side: Option<&RawStr>) -> content::Html<String> {
+  let instructions = limit::page();
+  let results = match limit::raw(a_str, input_str, side) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the function f(x) = {}:<br>{}",
+      instructions,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut items = "".to_string();
+  for (name, side) in &[("left", &results.left), ("right", &results.right)] {
+    if let Some(side) = side {
+      let text = match side.behavior.as_str() {
+        "converges" => format!("{} &plusmn; {:.1e}", side.value, side.error),
+        "does not converge" => format!("does not converge (the best estimate is {} &plusmn; {:.1e})", side.value, side.error),
+        behavior => behavior.to_string(),
+      };
+      items = format!("{}<li>from the {}: {}</li>", items, name, text);
+    }
+  }
+  let value = match results.value {
+    Some(value) => format!("The limit is {}.", value),
+    None => "The limit does not exist.".to_string(),
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> for the limit as x approaches {} of the function f(x) = {}:
+    <ul>{}</ul>{}",
+    instructions,
+    results.a,
+    expression,
+    items,
+    value,
+  ))
+}
+
+#[get("/implicit-differentiation/json/<point_str>/<input_str>")]
+fn implicit_differentiation_json(point_str: &RawStr, input_str: &RawStr) -> String {
+  match implicit_differentiation::raw(point_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/multiple-integration/json/<limits_str>/<input_str>?<query..>")]
+fn multiple_integration_json(limits_str: &RawStr, input_str: &RawStr, query: LenientForm<integration::Query>) -> String {
+  match multiple_integration::raw(limits_str, input_str, &query) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/geometry/arc-length/json/<xi_str>/<xf_str>/<input_str>")]
+fn geometry_arc_length_json(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> String {
+  match geometry::arc_length(xi_str, xf_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/geometry/area/json/<xi_str>/<xf_str>/<f_str>/<g_str>")]
+fn geometry_area_json(xi_str: &RawStr, xf_str: &RawStr, f_str: &RawStr, g_str: &RawStr) -> String {
+  match geometry::area(xi_str, xf_str, f_str, g_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/geometry/revolution/json/<axis_str>/<xi_str>/<xf_str>/<input_str>")]
+fn geometry_revolution_json(axis_str: &RawStr, xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> String {
+  match geometry::revolution(axis_str, xi_str, xf_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/monte-carlo/json/<limits_str>/<input_str>?<samples>&<seed>&<method>")]
+fn monte_carlo_json(
+  limits_str: &RawStr,
+  input_str: &RawStr,
+  samples: Option<&RawStr>,
+  seed: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> String {
+  match monte_carlo::raw(limits_str, input_str, samples, seed, method) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[post("/tabulated", data = "<data>")]
+fn tabulated_json(data: Data) -> String {
+  // One byte more than the limit is read, in order to detect a table which exceeds it.
+  let mut bytes = vec![];
+  if let Err(error) = data.open().take(tabulated::MAX_BYTES + 1).read_to_end(&mut bytes) {
+    return format!("{{\"message\": Cannot read the table: {}}}", error);
+  }
+  if bytes.len() as u64 > tabulated::MAX_BYTES {
+    return format!("{{\"message\": The table is too large: it may contain at most {} bytes.}}", tabulated::MAX_BYTES);
+  }
+  let body = match String::from_utf8(bytes) {
+    Ok(body) => body,
+    Err(error) => return format!("{{\"message\": Cannot read the table: {}}}", error),
+  };
+  match tabulated::raw(&body) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/fourier/json/<period_str>/<nterms_str>/<input_str>?<start>")]
+fn fourier_json(period_str: &RawStr, nterms_str: &RawStr, input_str: &RawStr, start: Option<&RawStr>) -> String {
+  match fourier::raw(period_str, nterms_str, input_str, start) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/implicit-differentiation/<point_str>/<input_str>")]
+fn implicit_differentiation_html(point_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let instructions = implicit_differentiation::page();
+  let results = match implicit_differentiation::raw(point_str, input_str) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the equation {}:<br>{}",
+      instructions,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  // A point off of the curve lies on some other level curve of the function.
+  let text = if results.value.abs() <= 1e-9 * results.point[0].abs().max(results.point[1].abs()).max(1.) {"".to_string()} else {
+    format!("<br>(The point does not lie on the curve, because the difference between the two sides of the equation is {} there.)", results.value)
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> at (x, y) = ({}, {}) for the equation {}:{}
+    <ul><li>dy/dx = {} &plusmn; {:.1e}</li><li>d<sup>2</sup>y/dx<sup>2</sup> = {} &plusmn; {:.1e}</li></ul>",
+    instructions,
+    results.point[0],
+    results.point[1],
+    expression,
+    text,
+    results.first.value,
+    results.first.error,
+    results.second.value,
+    results.second.error,
+  ))
+}
+
+#[get("/multiple-integration/<limits_str>/<input_str>?<query..>")]
+fn multiple_integration_html(limits_str: &RawStr, input_str: &RawStr, query: LenientForm<integration::Query>) -> content::Html<String> {
+  let instructions = multiple_integration::page();
+  let results = match multiple_integration::raw(limits_str, input_str, &query) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the limits {} and the function {}:<br>{}",
+      instructions,
+      limits_str,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let variables = ["x", "y", "z"];
+  let mut integrals = "".to_string();
+  let mut differentials = "".to_string();
+  for (variable, limits) in variables.iter().zip(&results.limits) {
+    let mut bounds = vec![];
+    for limit in limits {
+      let mut bound = limit.to_string();
+      for stri in ["div", "DIV", "d", "D"] {
+        bound = str::replace(&bound, stri, "/");
+      }
+      bounds.push(bound);
+    }
+    integrals = format!("{}&int;<sub>{}</sub><sup>{}</sup>", integrals, bounds[0], bounds[1]);
+    differentials = format!(" d{}{}", variable, differentials);
+  }
+  let convergence = if results.converged {"".to_string()} else {
+    format!("<br><b>At least one of the nested integrals did not converge</b> to an absolute accuracy of {}, so this is the best estimate.", results.epsilon)
+  };
+  content::Html(format!(
+    "{}<br><br><b>result</b>: {}{}{} = {}, with an estimated error of {:.1e}.<br>This required {} evaluations of the function.{}",
+    instructions,
+    integrals,
+    str::replace(&expression, "X", "x"),
+    differentials,
+    results.integral,
+    results.error,
+    results.evaluations,
+    convergence,
+  ))
+}
+
+// The html for any geometric result is the same, apart from a description of the curve(s).
+fn geometry_html(description: String, results: Result<geometry::Results, String>) -> content::Html<String> {
+  let instructions = geometry::page();
+  let results = match results {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for {}:<br>{}",
+      instructions,
+      description,
+      message
+    )),
+  };
+  let intersections = if results.intersections.is_empty() {"".to_string()} else {
+    format!(
+      " (which intersect at x = {})",
+      results.intersections.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "),
+    )
+  };
+  let mut items = "".to_string();
+  for quantity in &results.quantities {
+    let convergence = if quantity.converged {""} else {" (<b>The integral did not converge</b>, so this is the best estimate.)"};
+    items = format!(
+      "{}<li>{} = {} &plusmn; {:.1e}, which is <tt>{}</tt>{}</li>",
+      items,
+      quantity.name,
+      quantity.value,
+      quantity.error,
+      quantity.setup,
+      convergence,
+    );
+  }
+  content::Html(format!(
+    "{}<br><br><b>results</b> for {}{} from x = {} to x = {}:<ul>{}</ul>",
+    instructions,
+    description,
+    intersections,
+    results.xi,
+    results.xf,
+    items,
+  ))
+}
+
+#[get("/geometry/arc-length/<xi_str>/<xf_str>/<input_str>")]
+fn geometry_arc_length_html(xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  geometry_html(
+    format!("the curve y = {}", input_str),
+    geometry::arc_length(xi_str, xf_str, input_str),
+  )
+}
+
+#[get("/geometry/area/<xi_str>/<xf_str>/<f_str>/<g_str>")]
+fn geometry_area_html(xi_str: &RawStr, xf_str: &RawStr, f_str: &RawStr, g_str: &RawStr) -> content::Html<String> {
+  geometry_html(
+    format!("the curves y = {} and y = {}", f_str, g_str),
+    geometry::area(xi_str, xf_str, f_str, g_str),
+  )
+}
+
+#[get("/geometry/revolution/<axis_str>/<xi_str>/<xf_str>/<input_str>")]
+fn geometry_revolution_html(axis_str: &RawStr, xi_str: &RawStr, xf_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  geometry_html(
+    format!("the curve y = {} revolved about the {}-axis", input_str, axis_str),
+    geometry::revolution(axis_str, xi_str, xf_str, input_str),
+  )
+}
+
+#[get("/monte-carlo/<limits_str>/<input_str>?<samples>&<seed>&<method>")]
+fn monte_carlo_html(
+  limits_str: &RawStr,
+  input_str: &RawStr,
+  samples: Option<&RawStr>,
+  seed: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = monte_carlo::page();
+  let results = match monte_carlo::raw(limits_str, input_str, samples, seed, method) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the limits {} and the function {}:<br>{}",
+      instructions,
+      limits_str,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut ranges = vec![];
+  for (i, limit) in results.limits.iter().enumerate() {
+    ranges.push(format!("{} &le; x<sub>{}</sub> &le; {}", limit[0], i + 1, limit[1]));
+  }
+  let points = if results.method == "halton" {"quasi-random (Halton)"} else {"pseudo-random"};
+  content::Html(format!(
+    "{}<br><br><b>result</b>: the integral of the function f = {} over the box {} is {} &plusmn; {:.1e} (standard error).<br>This used {} {} points, with a seed of {}.",
+    instructions,
+    expression,
+    ranges.join(", "),
+    results.integral,
+    results.error,
+    results.samples,
+    points,
+    results.seed,
+  ))
+}
+
+#[get("/laplace/json/<s_str>/<input_str>?<query..>")]
+fn laplace_transform_json(s_str: &RawStr, input_str: &RawStr, query: LenientForm<integration::Query>) -> String {
+  match laplace::transform(s_str, input_str, &query) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/laplace/inverse/json/<ti_str>/<tf_str>/<n_str>/<input_str>")]
+fn laplace_inverse_json(ti_str: &RawStr, tf_str: &RawStr, n_str: &RawStr, input_str: &RawStr) -> String {
+  match laplace::inverse(ti_str, tf_str, n_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+fn laplace_html(description: String, results: Result<laplace::Results, String>) -> content::Html<String> {
+  let instructions = laplace::page();
+  let results = match results {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for {}:<br>{}",
+      instructions,
+      description,
+      message
+    )),
+  };
+  let (point, value) = if results.inverse {("t", "f(t)")} else {("s", "F(s)")};
+  let mut rows = "".to_string();
+  for row in &results.rows {
+    rows = format!("{}<tr><td>{}</td><td>{}</td><td>{:.1e}</td></tr>", rows, row.point, row.value, row.error);
+  }
+  let convergence = if results.converged {""} else {
+    "<br><b>At least one of the integrals did not converge</b>, so some of these are only the best estimates."
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> for {}:
+    <table border=1><tr><th>{}</th><th>{}</th><th>error</th></tr>{}</table>{}",
+    instructions,
+    description,
+    point,
+    value,
+    rows,
+    convergence,
+  ))
+}
+
+#[get("/laplace/<s_str>/<input_str>?<query..>")]
+fn laplace_transform_html(s_str: &RawStr, input_str: &RawStr, query: LenientForm<integration::Query>) -> content::Html<String> {
+  let description = format!("the Laplace transform of the function f(t) = {}", input_str);
+  laplace_html(description, laplace::transform(s_str, input_str, &query))
+}
+
+#[get("/laplace/inverse/<ti_str>/<tf_str>/<n_str>/<input_str>")]
+fn laplace_inverse_html(ti_str: &RawStr, tf_str: &RawStr, n_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let description = format!("the inverse Laplace transform of the function F(s) = {}", input_str);
+  laplace_html(description, laplace::inverse(ti_str, tf_str, n_str, input_str))
+}
+
+#[get("/fourier/<period_str>/<nterms_str>/<input_str>?<start>")]
+fn fourier_html(period_str: &RawStr, nterms_str: &RawStr, input_str: &RawStr, start: Option<&RawStr>) -> content::Html<String> {
+  let instructions = fourier::page();
+  let results = match fourier::raw(period_str, nterms_str, input_str, start) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the Fourier series of the function f(x) = {} with a period of {}:<br>{}",
+      instructions,
+      input_str,
+      period_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for term in &results.terms {
+    rows = format!("{}<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.1e}</td></tr>", rows, term.n, term.a, term.b, term.error);
+  }
+  let convergence = if results.converged {""} else {
+    "<br><b>At least one of the integrals did not converge</b>, so some of these are only the best estimates."
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> for the Fourier series of the function f(x) = {} over the period from x = {} to {}:
+    <table border=1><tr><th>n</th><th>a<sub>n</sub></th><th>b<sub>n</sub></th><th>error</th></tr>{}</table>
+    The partial sum is S(x) = {}<br>The square root of the integral of (f(x) - S(x))<sup>2</sup> over one period is {:.3e}, compared with {:.3e} for f(x)<sup>2</sup>.{}",
+    instructions,
+    str::replace(&expression, "X", "x"),
+    results.start,
+    results.start + results.period,
+    rows,
+    results.series,
+    results.l2_error,
+    results.l2_norm,
+    convergence,
+  ))
+}
+
+#[get("/jacobian/<point_str>/<input_str>")]
+fn jacobian_html(point_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let instructions = jacobian::page();
+  let results = match jacobian::raw(point_str, input_str) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!("{}<br><br><b>result</b> for the functions {}:<br>{}",
+      instructions,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let names = &helper::VARIABLES[..results.point.len()];
+  let mut rows = "".to_string();
+  for (i, row) in results.jacobian.iter().enumerate() {
+    let mut cells = format!("<td>f<sub>{}</sub> = {}</td>", i + 1, results.values[i]);
+    for estimate in row {
+      cells = format!("{}<td>{} &plusmn; {:.1e}</td>", cells, estimate.value, estimate.error);
+    }
+    rows = format!("{}<tr>{}</tr>", rows, cells);
+  }
+  let mut header = "<th></th>".to_string();
+  for name in names {
+    header = format!("{}<th>&part;/&part;{}</th>", header, name);
+  }
+  let determinant = match results.determinant {
+    Some(determinant) => format!("<br>The determinant of the Jacobian matrix is {}.", determinant),
+    None => "".to_string(),
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> at ({}) = ({}) for the functions ({}):
+    <br>The Jacobian matrix is<table border=1><tr>{}</tr>{}</table>{}",
+    instructions,
+    names.join(", "),
+    results.point.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "),
+    expression,
+    header,
+    rows,
+    determinant,
+  ))
+}
+
+#[get("/integration/<xi_str>/<xf_str>/<input_str>?<pv>&<history>&<query..>")]
+fn integration_html(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  pv: Option<&RawStr>,
+  history: Option<&RawStr>,
+  query: LenientForm<integration::Query>,
+) -> content::Html<String> {
+  let instructions = integration::page();
+  let results = match integration::raw(xi_str, xf_str, input_str, &query, pv, history) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the integral from x = {} to
+      x = {} of the function f(x) = {}:<br>{}",
+      instructions,
+      xi_str,
+      xf_str,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let accuracy = if results.rel_epsilon == 0. {
+    format!("an absolute accuracy of {}", results.epsilon)
+  } else {
+    format!("an absolute accuracy of {} or a relative accuracy of {}", results.epsilon, results.rel_epsilon)
+  };
+  // A principal value may require more than one rule.
+  let rule = if results.method.contains('+') {
+    format!("{} rules", str::replace(&results.method, "+", " and "))
+  } else if let Some(points) = results.points {
+    format!("{}-point {} rule", points, results.method)
+  } else {
+    format!("{} rule", results.method)
+  };
+  let convergence = if results.converged {
+    format!("Convergence of the {} to {} required", rule, accuracy)
+  } else {
+    format!("<b>The {} did not converge</b> to {}, so this is the best estimate after", rule, accuracy)
+  };
+  let integral = match &results.pv {
+    None => "the definite integral".to_string(),
+    Some(poles) => format!(
+      "the Cauchy principal value (about x = {}) of the integral",
+      poles.iter().map(|pole| pole.to_string()).collect::<Vec<String>>().join(", "),
+    ),
+  };
+  let history = match &results.history {
+    None => "".to_string(),
+    Some(_) if results.pv.is_some() => "<br>No history is available for a principal value.".to_string(),
+    Some(levels) if levels.is_empty() => "<br>No history is available, because Simpson's rule was not used.".to_string(),
+    Some(levels) => {
+      let mut rows = "".to_string();
+      for level in levels {
+        let difference = match level.difference {
+          Some(difference) => format!("{:.1e}", difference),
+          None => "".to_string(),
+        };
+        rows = format!(
+          "{}<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+          rows,
+          level.subdivisions, level.simpson, level.aitken, difference,
+        );
+      }
+      format!(
+        "<table border=1><tr><th>subdivisions</th><th>Simpson's rule</th><th>with Aitken's correction</th><th>difference</th></tr>{}</table>",
+        rows,
+      )
+    },
+  };
+  content::Html(format!(
+    "{}<br><br><b>result</b>: {} equals {} from x = {}
+    to x = {} of the function f(x) = {}, with an estimated error of {:.1e}.<br>{} {} subdivisions and {} evaluations of the function.{}",
+    instructions,
+    results.integral,
+    integral,
+    results.xi,
+    results.xf,
+    str::replace(&expression, "X", "x"),
+    results.error,
+    convergence,
+    results.subdivisions,
+    results.evaluations,
+    history,
+  ))
+}
+
+#[get("/integration/compare/<xi_str>/<xf_str>/<input_str>?<methods>&<query..>")]
+fn integration_compare_html(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  methods: Option<&RawStr>,
+  query: LenientForm<integration::Query>,
+) -> content::Html<String> {
+  let instructions = integration::page();
+  let comparison = match integration::compare(xi_str, xf_str, input_str, methods, &query) {
+    Ok(comparison) => comparison,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the comparison of rules for the integral from x = {} to
+      x = {} of the function f(x) = {}:<br>{}",
+      instructions,
+      xi_str,
+      xf_str,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let accuracy = if comparison.rel_epsilon == 0. {
+    format!("an absolute accuracy of {}", comparison.epsilon)
+  } else {
+    format!("an absolute accuracy of {} or a relative accuracy of {}", comparison.epsilon, comparison.rel_epsilon)
+  };
+  let mut rows = "".to_string();
+  for rule in &comparison.rules {
+    let method = if rule.method == "gauss-legendre" {
+      format!("{}-point {}", comparison.points, rule.method)
+    } else {
+      rule.method.clone()
+    };
+    rows = match &rule.message {
+      Some(message) => format!("{}<tr><td>{}</td><td colspan=5>{}</td></tr>", rows, method, message),
+      None => format!(
+        "{}<tr><td>{}</td><td>{}</td><td>{:.1e}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        rows,
+        method, rule.integral, rule.error, rule.subdivisions, rule.evaluations,
+        if rule.converged {"yes"} else {"<b>no</b>"},
+      ),
+    };
+  }
+  content::Html(format!(
+    "{}<br><br><b>results</b> for the definite integral from x = {} to x = {} of the function f(x) = {}, with {}:
+    <table border=1><tr><th>rule</th><th>integral</th><th>error</th><th>subdivisions</th><th>evaluations</th><th>converged</th></tr>{}</table>",
+    instructions,
+    comparison.xi,
+    comparison.xf,
+    str::replace(&expression, "X", "x"),
+    accuracy,
+    rows,
+  ))
+}
+
+#[get("/integration/oscillatory/<weight_str>/<omega_str>/<xi_str>/<xf_str>/<input_str>?<query..>")]
+fn integration_oscillatory_html(
+  weight_str: &RawStr,
+  omega_str: &RawStr,
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  input_str: &RawStr,
+  query: LenientForm<integration::Query>,
+) -> content::Html<String> {
+  let instructions = integration::page();
+  let results = match integration::oscillatory(weight_str, omega_str, xi_str, xf_str, input_str, &query) {
+    Ok(results) => results,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the integral from x = {} to
+      x = {} of the function f(x) = {} times {}({}x):<br>{}",
+      instructions,
+      xi_str,
+      xf_str,
+      input_str,
+      weight_str,
+      omega_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let convergence = if results.converged {"Convergence of Filon's rule required"} else {
+    "<b>Filon's rule did not converge</b>, so this is the best estimate after"
+  };
+  content::Html(format!(
+    "{}<br><br><b>result</b>: {} equals the definite integral from x = {}
+    to x = {} of the function f(x) = {} times {}({}x), with an estimated error of {:.1e}.<br>{} {} subdivisions and {} evaluations of the function.",
+    instructions,
+    results.integral,
+    results.xi,
+    results.xf,
+    str::replace(&expression, "X", "x"),
+    weight_str.to_lowercase(),
+    omega_str,
+    results.error,
+    convergence,
+    results.subdivisions,
+    results.evaluations,
+  ))
+}
+
+#[get("/integration/cumulative/<xi_str>/<xf_str>/<n_str>/<input_str>?<query..>")]
+fn integration_table_html(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  query: LenientForm<integration::Query>,
+) -> content::Html<String> {
+  let instructions = integration::page();
+  let table = match integration::table(xi_str, xf_str, n_str, input_str, &query) {
+    Ok(table) => table,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the cumulative integral of the function f(x) = {}
+      from x = {} to x = {}:<br>{}",
+      instructions,
+      input_str,
+      xi_str,
+      xf_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for row in &table.rows {
+    rows = format!(
+      "{}<div>{}</div><div>{}</div><div>{:.1e}</div>",
+      rows,
+      row.x, row.integral, row.error,
+    );
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat(3, 1fr); width:700px'>
+      <div>
+        <i>x</I>
+      </div>
+      <div>
+        <i>F</i>(<i>x</i>)
+      </div>
+      <div>
+        error
+      </div>
+    </div>
+    <div style='
+      height:200px;
+      width:700px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat(3, 1fr);
+    '>
+      {}
+    </div></div>", rows);
+  rows = format!(
+    "<div style='display: flex;
+    justify-content: center;'>{}</div>",
+    rows,
+  );
+  let convergence = if table.converged {"".to_string()} else {
+    "<br><b>The integral over at least one interval did not converge</b>, so the values are the best estimates.".to_string()
+  };
+  content::Html(format!(
+    "{}<br><br><b>results</b> for the integral F(x) of the function f(x) = {}
+    from x = {}, which required {} evaluations of the function:{}<br>{}",
+    instructions,
+    expression,
+    table.xi,
+    table.evaluations,
+    convergence,
+    rows,
   ))
 }
 
@@ -397,9 +1541,26 @@ fn ode2_html(
 
 fn main() {
   rocket::ignite().mount("/", routes![index,
-  differentiation_page, differentiation_json, differentiation_html,integration_page, integration_json, integration_html,
+  differentiation_page, differentiation_json, differentiation_html,
+  differentiation_table_json, differentiation_table_csv, differentiation_table_html,
+  integration_page, integration_json, integration_html,
+  integration_table_json, integration_table_csv, integration_table_html,
+  integration_oscillatory_json, integration_oscillatory_html,
+  integration_compare_json, integration_compare_html,
   root_finding_page, root_finding_json, root_finding_html,
   max_finding_page, max_finding_json, max_finding_html,
   ode_page, ode_json, ode_html,
-  ode2_page, ode2_json, ode2_html]).launch();
+  ode2_page, ode2_json, ode2_html,
+  partial_differentiation_page, partial_differentiation_json, partial_differentiation_html,
+  jacobian_page, jacobian_json, jacobian_html,
+  taylor_page, taylor_json, taylor_html,
+  limit_page, limit_json, limit_html,
+  implicit_differentiation_page, implicit_differentiation_json, implicit_differentiation_html,
+  multiple_integration_page, multiple_integration_json, multiple_integration_html,
+  geometry_page, geometry_arc_length_json, geometry_area_json, geometry_revolution_json,
+  geometry_arc_length_html, geometry_area_html, geometry_revolution_html,
+  monte_carlo_page, monte_carlo_json, monte_carlo_html,
+  tabulated_page, tabulated_json,
+  fourier_page, fourier_json, fourier_html,
+  laplace_page, laplace_transform_json, laplace_inverse_json, laplace_transform_html, laplace_inverse_html]).launch();
 }
@@ -0,0 +1,186 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "MONTE CARLO INTEGRATION".to_string(),
+		links: calculus::links(15),
+		instructions: "This page estimates the integral of a function of several variables over a rectangular box, for which (in more than three dimensions) the methods of the multiple integration page would require far too many evaluations of the function.  In the url bar after <tt>https://basic-calculus.herokuapp.com/monte-carlo</tt> type the following:<p align=center><tt>&sol;&lt;limits&gt;&sol;&lt;function of <i>x</i><sub>1</sub>, <i>x</i><sub>2</sub>, ...&gt;</tt></p>The limits are a comma-separated list of the lower and upper limits for <i>x</i><sub>1</sub>, then those for <i>x</i><sub>2</sub>, and so on for as many as 20 variables, which are written <tt>x1</tt>, <tt>x2</tt>, ... in the function.  (The first four may also be written <tt>x</tt>, <tt>y</tt>, <tt>z</tt>, and <tt>w</tt>.)  By default the function is evaluated at 10000 pseudo-random points, but you may append <tt>?samples=&lt;number no greater than 100000&gt;</tt> to the url.  The points are reproducible, and you may change them by appending <tt>?seed=&lt;nonnegative integer&gt;</tt>.  To use quasi-random points instead, which usually give a much smaller error, append <tt>?method=halton</tt>.  (Separate these parameters with <tt>&amp;</tt> if more than one is used.)".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the limits)", helper::NOTE2),
+		example: "To integrate the function <i>x</i><sub>1</sub><i>x</i><sub>2</sub><i>x</i><sub>3</sub><i>x</i><sub>4</sub><i>x</i><sub>5</sub> over the unit cube in five dimensions, type <tt>/0,1,0,1,0,1,0,1,0,1/x1*x2*x3*x4*x5</tt> after the current url address.  The result should be close to <tt>0.03125</tt> (ie, 1/32), with a standard error of about <tt>0.0005</tt>.  Typing <tt>/0,1,0,1,0,1,0,1,0,1/x1*x2*x3*x4*x5?method=halton</tt> should give a similar result with a smaller standard error.".to_string(),
+		algorithm: "the volume of the box times the average of the function at randomly distributed points (from the SplitMix64 generator), with a standard error which is calculated from the variance of the function values.  The quasi-random points are those of the Halton sequence, whose coordinates are the radical inverses of successive integers in the first few prime bases.  These are not independent, so the standard error is instead calculated from the spread of 16 estimates, for each of which the whole sequence is shifted by a different random amount (modulo the size of the box)".to_string(),
+		json: "Type '/json' in the url bar immediately after 'monte-carlo' if you would like the result in this format rather than html.  A successful response will contain seven properties: 'limits' (an array containing, for each variable, an array of its lower and upper limits), 'method' (either 'random' or 'halton'), 'samples' (the number of evaluations of the function), 'seed' (an integer), 'volume' (that of the box), 'integral' (a float), and 'error' (its standard error).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub limits: Vec<Vec<f64>>,
+	pub method: String,
+	pub samples: i32,
+	pub seed: u64,
+	pub volume: f64,
+	pub integral: f64,
+	pub error: f64,
+}
+
+const SAMPLES: i32 = 10000; // default number of evaluations of the function
+const MAX_SAMPLES: i32 = 100000; // limited by the time required to evaluate the function
+const REPLICATES: i32 = 16; // number of randomly shifted copies of a quasi-random sequence
+const PRIMES: [u64; 20] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71];
+
+// SplitMix64, a small generator whose output passes the usual statistical tests
+struct Generator {
+	state: u64,
+}
+
+impl Generator {
+	// a number which is uniformly distributed between 0 and 1
+	fn next(&mut self) -> f64 {
+		self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^= z >> 31;
+		// The 53 most significant bits fill the mantissa.
+		(z >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
+
+// the digits of i in the given base, reflected about the radix point
+fn radical_inverse(mut i: u64, base: u64) -> f64 {
+	let mut inverse = 0.;
+	let mut scale = 1. / base as f64;
+	while i > 0 {
+		inverse += (i % base) as f64 * scale;
+		i /= base;
+		scale /= base as f64;
+	}
+	inverse
+}
+
+// the mean and the variance of the mean of some values, by Welford's algorithm
+struct Statistics {
+	count: f64,
+	mean: f64,
+	sum_of_squares: f64,
+}
+
+impl Statistics {
+	fn new() -> Statistics {
+		Statistics {count: 0., mean: 0., sum_of_squares: 0.}
+	}
+	fn add(&mut self, value: f64) {
+		self.count += 1.;
+		let delta = value - self.mean;
+		self.mean += delta / self.count;
+		self.sum_of_squares += delta * (value - self.mean);
+	}
+	fn error(&self) -> f64 {
+		(self.sum_of_squares / (self.count - 1.) / self.count).sqrt()
+	}
+}
+
+// Integrates over the box [a1, b1] x [a2, b2] x ..., with the points in the unit cube mapped onto it.
+pub fn integrate(function: &dyn Fn(&[f64]) -> Result<f64, String>, limits: &[Vec<f64>], samples: i32, seed: u64, halton: bool) -> Result<(f64, f64), String> {
+	let volume = limits.iter().fold(1., |product, limit| product * (limit[1] - limit[0]));
+	let mut generator = Generator {state: seed};
+	let evaluate = |unit: &[f64]| -> Result<f64, String> {
+		let point: Vec<f64> = unit.iter().zip(limits).map(|(u, limit)| limit[0] + u * (limit[1] - limit[0])).collect();
+		match function(&point) {
+			Ok(f) => Ok(f),
+			Err(message) => Err(format!("Cannot evaluate function at {:?}: {}", point, message)),
+		}
+	};
+	let mut statistics = Statistics::new();
+	if !halton {
+		for _ in 0..samples {
+			let unit: Vec<f64> = limits.iter().map(|_| generator.next()).collect();
+			statistics.add(match evaluate(&unit) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			});
+		}
+		return Ok((volume * statistics.mean, volume.abs() * statistics.error()));
+	}
+	// Each replicate is the same sequence, shifted randomly and wrapped around the unit cube.
+	for _ in 0..REPLICATES {
+		let shift: Vec<f64> = limits.iter().map(|_| generator.next()).collect();
+		let mut replicate = Statistics::new();
+		for i in 1..=(samples / REPLICATES) as u64 {
+			let unit: Vec<f64> = shift.iter().zip(&PRIMES).map(|(s, base)| (radical_inverse(i, *base) + s).fract()).collect();
+			replicate.add(match evaluate(&unit) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			});
+		}
+		statistics.add(replicate.mean);
+	}
+	Ok((volume * statistics.mean, volume.abs() * statistics.error()))
+}
+
+pub fn raw(limits_str: &RawStr, input_str: &RawStr, samples_str: Option<&RawStr>, seed_str: Option<&RawStr>, method_str: Option<&RawStr>) -> Result<Results, String> {
+	let limits_list = match helper::parse_list(&limits_str.to_string()) {
+		Ok(limits_list) => limits_list,
+		Err(message) => return Err(message),
+	};
+	if limits_list.len() % 2 == 1 || limits_list.is_empty() || limits_list.len() > 2 * PRIMES.len() {
+		return Err(format!("There must be two limits for each of between 1 and {} variables.", PRIMES.len()));
+	}
+	let limits: Vec<Vec<f64>> = limits_list.chunks(2).map(|pair| pair.to_vec()).collect();
+	let mut integers = vec![];
+	let bounds = [
+		("number of samples", samples_str, SAMPLES as f64, 2. * REPLICATES as f64, MAX_SAMPLES as f64),
+		("seed", seed_str, 0., 0., u32::MAX as f64),
+	];
+	for (name, integer_str, default, min, max) in &bounds {
+		integers.push(match integer_str {
+			None => *default,
+			Some(integer_str) => match helper::parse_expression(integer_str.to_string()) {
+				Ok(integer) => {
+					if integer.round() != integer {
+						return Err(format!("{} is not an integer.", integer));
+					} else if integer < *min || integer > *max {
+						return Err(format!("The {} must be between {} and {}.", name, min, max));
+					}
+					integer
+				},
+				Err(message) => return Err(message),
+			},
+		});
+	}
+	let (mut samples, seed) = (integers[0] as i32, integers[1] as u64);
+	let method = match method_str {
+		None => "random".to_string(),
+		Some(method_str) => {
+			let method = method_str.to_lowercase();
+			if method != "random" && method != "halton" {
+				return Err(format!("{} is not a method.  Use either random or halton.", method_str));
+			}
+			method
+		},
+	};
+	if method == "halton" {
+		samples -= samples % REPLICATES;
+	}
+	// Each of the first few variables has two names.
+	let numbered: Vec<String> = (1..=limits.len()).map(|i| format!("x{}", i)).collect();
+	let mut names: Vec<&str> = numbered.iter().map(|name| name.as_str()).collect();
+	let aliases = limits.len().min(helper::VARIABLES.len());
+	names.extend(&helper::VARIABLES[..aliases]);
+	let function = |values: &[f64]| -> Result<f64, String> {
+		let mut values = values.to_vec();
+		values.extend(values[..aliases].to_vec());
+		helper::function(input_str.to_string(), &names, &values)
+	};
+	let (integral, error) = match integrate(&function, &limits, samples, seed, method == "halton") {
+		Ok(estimate) => estimate,
+		Err(message) => return Err(message),
+	};
+	let volume = limits.iter().fold(1., |product, limit| product * (limit[1] - limit[0]));
+	Ok(Results {limits, method, samples, seed, volume, integral, error})
+}
@@ -0,0 +1,127 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+use std::cell::{Cell, RefCell};
+
+use crate::helper;
+use crate::integration::{self, Estimate, Settings};
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "MULTIPLE INTEGRATION".to_string(),
+		links: calculus::links(13),
+		instructions: "This page calculates the double integral &int;&int;<i>f</i>(<i>x</i>, <i>y</i>) <i>dy dx</i> or the triple integral &int;&int;&int;<i>f</i>(<i>x</i>, <i>y</i>, <i>z</i>) <i>dz dy dx</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/multiple-integration</tt> type the following:<p align=center><tt>&sol;&lt;limits&gt;&sol;&lt;function of <i>x</i>, <i>y</i>, and perhaps <i>z</i>&gt;</tt></p>The limits are a comma-separated list of the lower and upper limits for <i>x</i>, then those for <i>y</i>, and then (for a triple integral) those for <i>z</i>.  The limits for <i>x</i> must be numbers, but those for <i>y</i> may be functions of <i>x</i>, and those for <i>z</i> may be functions of <i>x</i> and <i>y</i>.  Any limit may be <tt>inf</tt> or <tt>-inf</tt>.  By default each integral is calculated by the 21-point Gauss-Kronrod rule, but the settings <tt>method</tt>, <tt>epsilon</tt>, <tt>rel_epsilon</tt>, and <tt>max_subdivisions</tt> of the integration page may be appended to the url, and they then apply to every one of the nested integrals.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the limits)", helper::NOTE2),
+		example: "To integrate the function <i>xy</i> over the triangle bounded by the axes and the line <i>x</i> + <i>y</i> = 1, type <tt>/0,1,0,1-x/xy</tt> after the current url address.  The result should be <tt>0.04166...</tt> (ie, 1/24).  Typing <tt>/0,1,0,1-x,0,1-x-y/1</tt> should give the volume of a tetrahedron, <tt>0.1666...</tt>, and typing <tt>/-inf,inf,-inf,inf/exp(-x**2-y**2)</tt> should give <tt>3.14159...</tt>".to_string(),
+		algorithm: "the integral over the innermost variable is calculated as on the integration page for each value of the outer variables which is required by the integral over the next variable, and so on.  The estimated error of the result is the sum of that of the outermost integral and (by the trapezoidal rule) the integral of the estimated errors of the inner ones".to_string(),
+		json: "Type '/json' in the url bar immediately after 'multiple-integration' if you would like the result in this format rather than html.  A successful response will contain nine properties: 'limits' (an array containing, for each variable, an array of two strings, its lower and upper limits), 'integral' (a float), 'error' (its estimated absolute error), 'method' (the rule used for the outermost integral), 'evaluations' (the number of times that the function was evaluated), 'epsilon', 'rel_epsilon', and 'max_subdivisions' (the settings, as on the integration page), and 'converged' (false if any of the nested integrals reached the cap on its number of subdivisions before converging).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub limits: Vec<Vec<String>>,
+	pub integral: f64,
+	pub error: f64,
+	pub method: String,
+	pub evaluations: i32,
+	pub epsilon: f64,
+	pub rel_epsilon: f64,
+	pub max_subdivisions: Option<i32>,
+	pub converged: bool,
+}
+
+// A limit is either infinite or a function of the variables outside of its integral.
+fn limit(limit_str: &str, outer: &[f64]) -> Result<f64, String> {
+	if outer.is_empty() {
+		return helper::parse_extended(limit_str);
+	}
+	match helper::function(limit_str.to_string(), &helper::VARIABLES[..outer.len()], outer) {
+		Ok(limit) => Ok(limit),
+		Err(message) => match helper::parse_extended(limit_str) {
+			Ok(limit) => Ok(limit),
+			Err(_) => Err(message),
+		},
+	}
+}
+
+// Integrates over the variable which follows those whose values are given in outer, by nesting
+// the integrals over the remaining variables within the integrand.
+pub fn nested(function: &dyn Fn(&[f64]) -> Result<f64, String>, limits: &[Vec<String>], outer: &[f64], settings: &Settings) -> Result<Estimate, String> {
+	let level = outer.len();
+	let mut bounds = vec![];
+	for limit_str in &limits[level] {
+		bounds.push(match limit(limit_str, outer) {
+			Ok(bound) => bound,
+			Err(message) => return Err(message),
+		});
+	}
+	let (lower, upper) = (bounds[0], bounds[1]);
+	let point = |value: f64| {
+		let mut point = outer.to_vec();
+		point.push(value);
+		point
+	};
+	if level == limits.len() - 1 {
+		return integration::integrate(&|value| function(&point(value)), lower, upper, settings);
+	}
+	// the estimated errors of the inner integrals, and how they were obtained
+	let errors = RefCell::new(vec![]);
+	let evaluations = Cell::new(0);
+	let converged = Cell::new(true);
+	let integrand = |value: f64| -> Result<f64, String> {
+		match nested(function, limits, &point(value), settings) {
+			Ok(estimate) => {
+				errors.borrow_mut().push((value, estimate.error));
+				evaluations.set(evaluations.get() + estimate.evaluations);
+				converged.set(converged.get() && estimate.converged);
+				Ok(estimate.integral)
+			},
+			Err(message) => Err(message),
+		}
+	};
+	let estimate = match integration::integrate(&integrand, lower, upper, settings) {
+		Ok(estimate) => estimate,
+		Err(message) => return Err(message),
+	};
+	// Only points at finite values of the variable can be ordered (and weighted) along the range.
+	let mut errors: Vec<(f64, f64)> = errors.into_inner().into_iter().filter(|(value, _)| value.is_finite()).collect();
+	errors.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+	let inner_error = errors.windows(2).fold(0., |sum, pair| sum + (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / 2.);
+	Ok(Estimate {
+		error: estimate.error + inner_error,
+		evaluations: evaluations.get(),
+		converged: estimate.converged && converged.get(),
+		..estimate
+	})
+}
+
+pub fn raw(limits_str: &RawStr, input_str: &RawStr, query: &integration::Query) -> Result<Results, String> {
+	let limits_list: Vec<String> = limits_str.as_str().split(',').map(|limit| limit.to_string()).collect();
+	if limits_list.len() != 4 && limits_list.len() != 6 {
+		return Err(format!("There must be two limits for each of two or three variables, not {} limits.", limits_list.len()));
+	}
+	let limits: Vec<Vec<String>> = limits_list.chunks(2).map(|pair| pair.to_vec()).collect();
+	let settings = match integration::settings(&integration::Query {points: None, ..*query}, "gk21") {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let names = &helper::VARIABLES[..limits.len()];
+	let function = |values: &[f64]| helper::function(input_str.to_string(), names, values);
+	let estimate = match nested(&function, &limits, &[], &settings) {
+		Ok(estimate) => estimate,
+		Err(message) => return Err(message),
+	};
+	Ok(Results {
+		limits,
+		integral: estimate.integral,
+		error: estimate.error,
+		method: estimate.method,
+		evaluations: estimate.evaluations,
+		epsilon: settings.epsilon,
+		rel_epsilon: settings.rel_epsilon,
+		max_subdivisions: settings.max_subdivisions,
+		converged: estimate.converged,
+	})
+}
@@ -0,0 +1,142 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::differentiation;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "PARTIAL DIFFERENTIATION".to_string(),
+		links: calculus::links(8),
+		instructions: "This page differentiates a function of as many as four variables: <i>x</i>, <i>y</i>, <i>z</i>, and <i>w</i>.  In the url bar after <tt>https://basic-calculus.herokuapp.com/partial-differentiation</tt> type the following:<p align=center><tt>&sol;&lt;comma-separated coordinates of the point at which to calculate the function and derivatives&gt;&sol;&lt;function of <i>x</I>, <i>y</i>, ...&gt;</tt></p>The number of coordinates determines the number of variables.  Besides the gradient vector and the Hessian matrix, you may calculate any mixed partial derivative by appending <tt>?wrt=&lt;variables&gt;</tt> to the url, in which each variable is repeated as many times as the function is to be differentiated with respect to it.  For instance <tt>?wrt=xxy</tt> represents &part;<sup>3</sup><i>f</i>/&part;<i>x</i><sup>2</sup>&part;<i>y</i>.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the coordinates of the point)", helper::NOTE2),
+		example: "To differentiate the function <i>x</i><sup>2</sup><i>y</i> + sin(<i>xy</i>) at (<i>x</i>, <i>y</i>) = (1, 2), type <tt>/1,2/x**2y+sin(xy)</tt> after the current url address.  The gradient should be <tt>(3.16..., 0.58...)</tt>, and the Hessian's elements should be <tt>0.36..., -0.23..., -0.23..., and -0.90...</tt>  Appending <tt>?wrt=xxy</tt> should also give <tt>0.027...</tt>".to_string(),
+		algorithm: "the same finite differences and extrapolation as on the differentiation page, with the stencil for each mixed partial derivative being the product of stencils for the individual variables".to_string(),
+		json: "Type '/json' in the url bar immediately after 'partial-differentiation' if you would like the result in this format rather than html.  A successful response will contain five properties: 'point' (an array of floats), 'value' (the function's value there), 'gradient' and 'hessian' (an array and a square array of floats, respectively, each element of which is an object with properties 'value' and 'error', the latter being the estimated absolute error), and 'partial' (either null or an object with properties 'wrt', 'value', and 'error', for the mixed partial derivative requested).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Estimate {
+	pub value: f64,
+	pub error: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Partial {
+	pub wrt: String,
+	pub value: f64,
+	pub error: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub point: Vec<f64>,
+	pub value: f64,
+	pub gradient: Vec<Estimate>,
+	pub hessian: Vec<Vec<Estimate>>,
+	pub partial: Option<Partial>,
+}
+
+// Calculates the mixed partial derivative of a function of several variables, for which
+// orders[i] is the number of times that the function is differentiated w.r.t. variable i.
+pub fn partial(function: &dyn Fn(&[f64]) -> Result<f64, String>, point: &[f64], orders: &[i32]) -> Result<Estimate, String> {
+	let nonsingular = function(point).is_ok();
+	// The product of one-dimensional stencils is a list of (offsets, weight) pairs.
+	let mut points: Vec<(Vec<f64>, f64)> = vec![(vec![], 1.)];
+	for order in orders {
+		let stencil = differentiation::central(*order, nonsingular);
+		let mut new_points = vec![];
+		for (offsets, weight) in &points {
+			for (offset, new_weight) in &stencil.points {
+				let mut new_offsets = offsets.clone();
+				new_offsets.push(*offset);
+				new_points.push((new_offsets, weight * new_weight));
+			}
+		}
+		points = new_points;
+	}
+	let order: i32 = orders.iter().sum();
+	let estimate = |h: f64| -> Result<(f64, f64), String> {
+		let mut sum = 0.;
+		let mut noise = 0.;
+		for (offsets, weight) in &points {
+			let coords: Vec<f64> = point.iter().zip(offsets).map(|(x, offset)| x + offset * h).collect();
+			let f = match function(&coords) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			};
+			sum += weight * f;
+			noise += (weight * f).abs();
+		}
+		let scale = h.powi(order);
+		Ok((sum / scale, f64::EPSILON * noise / scale))
+	};
+	let size = point.iter().fold(0_f64, |size, x| size.max(x.abs()));
+	match differentiation::differentiate(&estimate, differentiation::step(size, order), 2) {
+		Ok((value, error)) => Ok(Estimate {value, error}),
+		Err(message) => Err(message),
+	}
+}
+
+pub fn raw(point_str: &RawStr, input_str: &RawStr, wrt_str: Option<&RawStr>) -> Result<Results, String> {
+	let point = match helper::parse_list(&point_str.to_string()) {
+		Ok(point) => point,
+		Err(message) => return Err(message),
+	};
+	let n = point.len();
+	if n > helper::VARIABLES.len() {
+		return Err(format!("A function may have no more than {} variables.", helper::VARIABLES.len()));
+	}
+	let names = &helper::VARIABLES[..n];
+	let function = |coords: &[f64]| helper::function(input_str.to_string(), names, coords);
+	let value = match function(&point) {
+		Ok(value) => value,
+		Err(message) => return Err(message),
+	};
+	// orders of differentiation w.r.t. each variable, for a particular derivative
+	let unit = |i: usize| -> Vec<i32> {(0..n).map(|j| if j == i {1} else {0}).collect()};
+	let mut gradient = vec![];
+	for i in 0..n {
+		gradient.push(match partial(&function, &point, &unit(i)) {
+			Ok(estimate) => estimate,
+			Err(message) => return Err(message),
+		});
+	}
+	let mut hessian: Vec<Vec<Estimate>> = vec![];
+	for i in 0..n {
+		// The Hessian is symmetric, so the entries before the diagonal are those below it in the rows above.
+		let mut row: Vec<Estimate> = hessian.iter().map(|above| Estimate {value: above[i].value, error: above[i].error}).collect();
+		for j in i..n {
+			let orders: Vec<i32> = unit(i).iter().zip(unit(j)).map(|(a, b)| a + b).collect();
+			row.push(match partial(&function, &point, &orders) {
+				Ok(estimate) => estimate,
+				Err(message) => return Err(message),
+			});
+		}
+		hessian.push(row);
+	}
+	let mixed = match wrt_str {
+		None => None,
+		Some(wrt_str) => {
+			let wrt = wrt_str.to_string().to_lowercase();
+			let mut orders = vec![0; n];
+			for char in wrt.chars() {
+				match names.iter().position(|name| name.starts_with(char)) {
+					Some(i) => orders[i] += 1,
+					None => return Err(format!("{} is not one of the variables: {}.", char, names.join(", "))),
+				};
+			}
+			if orders.iter().sum::<i32>() > differentiation::MAX_ORDER {
+				return Err(format!("The order may not exceed {}.", differentiation::MAX_ORDER));
+			}
+			match partial(&function, &point, &orders) {
+				Ok(Estimate {value, error}) => Some(Partial {wrt, value, error}),
+				Err(message) => return Err(message),
+			}
+		},
+	};
+	Ok(Results {point, value, gradient, hessian, partial: mixed})
+}
@@ -69,11 +69,28 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 		}
 	}
 	// Second, find a root that has been bracketed.
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let (x, root_steps) = match refine(&function, x0, x2, f0, f2, epsilon) {
+		Ok(root) => root,
+		Err(message) => return Err(message),
+	};
+	Ok(Results {
+		xi,
+		x,
+		bracket_steps,
+		root_steps,
+		epsilon,
+	})
+}
+
+// Finds a root which is bracketed by x0 < x2, at which the function has values f0 and f2, and
+// returns it along with the number of steps required.
+pub fn refine(function: &dyn Fn(f64) -> Result<f64, String>, mut x0: f64, mut x2: f64, mut f0: f64, mut f2: f64, epsilon: f64) -> Result<(f64, i32), String> {
 	let root_steps_max = 20;
 	let mut root_steps = 0;
 	// Utilize a third point, to allow inverse-quadratic interpolation.
 	let mut x1 = (x0 + x2) / 2.;
-	let mut f1 = match helper::function1(input_str.to_string(), x1) {
+	let mut f1 = match function(x1) {
 		Ok(f1) => f1,
 		Err(message) => return Err(message),
 	};
@@ -87,7 +104,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 		if bisect {
 			if f0 * f1 > 0. {
 				let xc = (x1 + x2) / 2.;
-				let fc = match helper::function1(input_str.to_string(), xc) {
+				let fc = match function(xc) {
 					Ok(fc) => fc,
 					Err(message) => return Err(message),
 				};
@@ -100,7 +117,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 				}
 			} else {
 				let xc = (x1 + x0) / 2.;
-				let fc = match helper::function1(input_str.to_string(), xc) {
+				let fc = match function(xc) {
 					Ok(fc) => fc,
 					Err(message) => return Err(message),
 				};
@@ -121,7 +138,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 			if xc < x0 || xc > x2 {
 				continue;
 			}
-			let fc = match helper::function1(input_str.to_string(), xc) {
+			let fc = match function(xc) {
 				Ok(fc) => fc,
 				Err(message) => return Err(message),
 			};
@@ -155,11 +172,5 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 	} else {
 		x1 = if f2.abs() < f1.abs() {x2} else {x1};
 	}
-	Ok(Results {
-		xi,
-		x: x1,
-		bracket_steps,
-		root_steps,
-		epsilon,
-	})
+	Ok((x1, root_steps))
 }
@@ -0,0 +1,202 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use crate::helper;
+use crate::differentiation;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "TABULATED DATA".to_string(),
+		links: calculus::links(16),
+		instructions: "This page integrates and differentiates a function which is known only by its values at a set of points, which need not be equally spaced.  Send the table in the body of a POST request to <tt>https://basic-calculus.herokuapp.com/tabulated</tt>, either as csv (one line of the form <tt>&lt;x&gt;,&lt;y&gt;</tt> for each point, optionally preceded by a line of column headings) or as json (either an array of <tt>[x, y]</tt> pairs or an object whose properties 'x' and 'y' are arrays of equal length).  For instance, from the command line you may type <tt>curl --data-binary @data.csv https://basic-calculus.herokuapp.com/tabulated</tt>.  The points may be in any order, but no two may have the same value of <i>x</i>, and there must be at least two of them.  The table may contain at most 1048576 bytes.".to_string(),
+		note: "Unlike the url of the other pages, the table may contain numbers in scientific notation (eg, <tt>6.02e23</tt>), but not expressions.".to_string(),
+		example: "Sending the csv table <tt>x,y</tt> / <tt>0,0</tt> / <tt>0.5,0.25</tt> / <tt>1.5,2.25</tt> / <tt>2,4</tt> (ie, <i>y</i> = <i>x</i><sup>2</sup>, with each pair on its own line) should give integrals of <tt>2.875</tt> by the trapezoidal rule, <tt>2.666...</tt> (ie, 8/3, which is exact) by Simpson's rule, and <tt>2.664...</tt> by the spline, and a first derivative of <tt>1</tt> at <i>x</i> = 0.5.".to_string(),
+		algorithm: "the trapezoidal rule; Simpson's rule for unequal intervals, which integrates the parabola through each successive pair of intervals (and, for an odd number of intervals, the parabola through the last three points over the last interval); and the integral of the natural cubic spline through the points.  The derivatives at each point are those of the parabola through it and its two nearest neighbors, whose weights are found by Fornberg's algorithm as on the differentiation page".to_string(),
+		json: "The response is always in json format.  A successful response will contain seven properties: 'x' and 'y' (arrays of floats, sorted in order of increasing x), 'first' and 'second' (arrays of floats whose values are the first and second derivatives at each point), and 'trapezoid', 'simpson' (null if there are only two points), and 'spline' (floats, the integrals from the smallest to the largest value of x).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub x: Vec<f64>,
+	pub y: Vec<f64>,
+	pub first: Vec<f64>,
+	pub second: Vec<f64>,
+	pub trapezoid: f64,
+	pub simpson: Option<f64>,
+	pub spline: f64,
+}
+
+pub const MAX_BYTES: u64 = 1 << 20; // largest table which is read
+const MAX_POINTS: usize = 100000;
+
+fn number(value: &Value) -> Result<f64, String> {
+	match value.as_f64() {
+		Some(number) => Ok(number),
+		None => Err(format!("{} is not a number.", value)),
+	}
+}
+
+fn parse_json(body: &str) -> Result<Vec<(f64, f64)>, String> {
+	let value: Value = match serde_json::from_str(body) {
+		Ok(value) => value,
+		Err(message) => return Err(format!("Cannot parse the json: {}", message)),
+	};
+	let mut points = vec![];
+	match (value.get("x").and_then(Value::as_array), value.get("y").and_then(Value::as_array), value.as_array()) {
+		(Some(xs), Some(ys), _) => {
+			if xs.len() != ys.len() {
+				return Err(format!("There are {} values of x but {} values of y.", xs.len(), ys.len()));
+			}
+			for (x, y) in xs.iter().zip(ys) {
+				points.push(match (number(x), number(y)) {
+					(Ok(x), Ok(y)) => (x, y),
+					(Err(message), _) | (_, Err(message)) => return Err(message),
+				});
+			}
+		},
+		(_, _, Some(pairs)) => for pair in pairs {
+			points.push(match pair.as_array() {
+				Some(pair) if pair.len() == 2 => match (number(&pair[0]), number(&pair[1])) {
+					(Ok(x), Ok(y)) => (x, y),
+					(Err(message), _) | (_, Err(message)) => return Err(message),
+				},
+				_ => return Err(format!("{} is not a pair of numbers.", pair)),
+			});
+		},
+		_ => return Err("The json must be either an array of pairs or an object with arrays 'x' and 'y'.".to_string()),
+	}
+	Ok(points)
+}
+
+fn parse_csv(body: &str) -> Result<Vec<(f64, f64)>, String> {
+	let mut points = vec![];
+	for (i, line) in body.lines().map(str::trim).filter(|line| !line.is_empty()).enumerate() {
+		let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+		if fields.len() != 2 {
+			return Err(format!("The line '{}' does not have two columns.", line));
+		}
+		match (fields[0].parse::<f64>(), fields[1].parse::<f64>()) {
+			(Ok(x), Ok(y)) => points.push((x, y)),
+			// Only the first line may be a heading.
+			_ => if i > 0 {
+				return Err(format!("The line '{}' does not contain two numbers.", line));
+			},
+		}
+	}
+	Ok(points)
+}
+
+// second derivatives of the natural cubic spline through the points, from the tridiagonal
+// equations for continuity of the first derivative (solved by the Thomas algorithm)
+fn spline(x: &[f64], y: &[f64]) -> Vec<f64> {
+	let n = x.len();
+	let mut m = vec![0.; n];
+	if n < 3 {
+		return m;
+	}
+	let mut diagonal = vec![0.; n];
+	let mut rhs = vec![0.; n];
+	for i in 1..n - 1 {
+		let (h0, h1) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+		diagonal[i] = 2. * (h0 + h1);
+		rhs[i] = 6. * ((y[i + 1] - y[i]) / h1 - (y[i] - y[i - 1]) / h0);
+		// Eliminate the subdiagonal, whose element in row i is h0.
+		if i > 1 {
+			let ratio = h0 / diagonal[i - 1];
+			diagonal[i] -= ratio * h0;
+			rhs[i] -= ratio * rhs[i - 1];
+		}
+	}
+	for i in (1..n - 1).rev() {
+		let superdiagonal = if i < n - 2 {x[i + 1] - x[i]} else {0.};
+		m[i] = (rhs[i] - superdiagonal * m[i + 1]) / diagonal[i];
+	}
+	m
+}
+
+// Simpson's rule for unequal intervals
+fn simpson(x: &[f64], y: &[f64]) -> f64 {
+	let n = x.len() - 1; // number of intervals
+	let mut integral = 0.;
+	for i in (0..n - 1).step_by(2) {
+		let (h0, h1) = (x[i + 1] - x[i], x[i + 2] - x[i + 1]);
+		integral += (h0 + h1) / 6. * ((2. - h1 / h0) * y[i] + (h0 + h1).powi(2) / (h0 * h1) * y[i + 1] + (2. - h0 / h1) * y[i + 2]);
+	}
+	// The last of an odd number of intervals is covered by the parabola through the last 3 points.
+	if n % 2 == 1 {
+		let (h0, h1) = (x[n - 1] - x[n - 2], x[n] - x[n - 1]);
+		let alpha = (2. * h1 * h1 + 3. * h1 * h0) / (6. * (h0 + h1));
+		let beta = (h1 * h1 + 3. * h1 * h0) / (6. * h0);
+		let eta = h1.powi(3) / (6. * h0 * (h0 + h1));
+		integral += alpha * y[n] + beta * y[n - 1] - eta * y[n - 2];
+	}
+	integral
+}
+
+pub fn raw(body: &str) -> Result<Results, String> {
+	let trimmed = body.trim();
+	let mut points = match if trimmed.starts_with('{') || trimmed.starts_with('[') {parse_json(trimmed)} else {parse_csv(trimmed)} {
+		Ok(points) => points,
+		Err(message) => return Err(message),
+	};
+	if points.len() < 2 || points.len() > MAX_POINTS {
+		return Err(format!("The table must have between 2 and {} points, not {}.", MAX_POINTS, points.len()));
+	}
+	if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+		return Err("Every value in the table must be finite.".to_string());
+	}
+	points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+	let x: Vec<f64> = points.iter().map(|point| point.0).collect();
+	let y: Vec<f64> = points.iter().map(|point| point.1).collect();
+	for pair in x.windows(2) {
+		if pair[0] == pair[1] {
+			return Err(format!("The value x = {} appears more than once.", pair[0]));
+		}
+	}
+	let n = x.len();
+	// derivatives of the parabola through each point and its nearest neighbors (or, at the ends
+	// of the table, the next two points), with offsets in units of h = 1
+	let mut first = vec![];
+	let mut second = vec![];
+	for i in 0..n {
+		let start = if n == 2 {0} else {i.max(1).min(n - 2) - 1};
+		let end = (start + 3).min(n);
+		let nodes: Vec<f64> = x[start..end].iter().map(|xj| xj - x[i]).collect();
+		let derivative = |order| {
+			let stencil = differentiation::stencil(order, nodes.clone());
+			stencil.points.iter().zip(&y[start..end]).fold(0., |sum, ((_, weight), yj)| sum + weight * yj)
+		};
+		first.push(derivative(1));
+		// A line through only two points has no curvature.
+		second.push(if nodes.len() > 2 {derivative(2)} else {0.});
+	}
+	let trapezoid = (0..n - 1).fold(0., |sum, i| sum + (x[i + 1] - x[i]) * (y[i] + y[i + 1]) / 2.);
+	let m = spline(&x, &y);
+	let spline = (0..n - 1).fold(0., |sum, i| {
+		let h = x[i + 1] - x[i];
+		sum + h * (y[i] + y[i + 1]) / 2. - h.powi(3) * (m[i] + m[i + 1]) / 24.
+	});
+	Ok(Results {
+		simpson: if n > 2 {Some(simpson(&x, &y))} else {None},
+		x,
+		y,
+		first,
+		second,
+		trapezoid,
+		spline,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn simpson_example() {
+		let results = raw("x,y\n0,0\n0.5,0.25\n1.5,2.25\n2,4\n").unwrap();
+		assert!((results.simpson.unwrap() - 8. / 3.).abs() < 1e-12);
+	}
+}
@@ -0,0 +1,135 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::differentiation;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "TAYLOR POLYNOMIALS".to_string(),
+		links: calculus::links(10),
+		instructions: "This page calculates the Taylor polynomial of a function about a particular point.  In the url bar after <tt>https://basic-calculus.herokuapp.com/taylor</tt> type the following:<p align=center><tt>&sol;&lt;value of <i>x</i> about which to expand the function&gt;&sol;&lt;order of the polynomial (no greater than 9)&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>To also estimate the largest possible remainder (ie, the difference between the function and the polynomial) within some distance <i>r</i> of the point, append <tt>?radius=&lt;r&gt;</tt> to the url.".to_string(),
+		note: format!("{}{}{}", helper::NOTE1, " (ie, the point and the radius)", helper::NOTE2),
+		example: "To calculate the 3rd-order Taylor polynomial of the function 2<i>x</i> + 3/(<i>x</i><sup>4</sup> + 5) about <i>x</i> = 1, type <tt>/1/3/2x+3d(x**4+5)?radius=0.5</tt> after the current url address.  The coefficients should be <tt>2.5, 1.66..., -0.27..., and 0.18...</tt>, and the remainder should be no larger than <tt>0.019...</tt>".to_string(),
+		algorithm: "the same finite differences and extrapolation as on the differentiation page, for the coefficients <i>f</i><sup>(<i>k</i>)</sup>(<i>x</i><sub>0</sub>)/<i>k</i>!.  The remainder is bounded by Lagrange's formula, max|<i>f</i><sup>(<i>n</i>+1)</sup>|<i>r</i><sup><i>n</i>+1</sup>/(<i>n</i> + 1)!, in which the maximum is estimated by sampling the derivative at equally spaced points within the radius".to_string(),
+		json: "Type '/json' in the url bar immediately after 'taylor' if you would like the result in this format rather than html.  A successful response will contain seven properties: 'x0' (a float), 'order' (an integer), 'coefficients' (an array of floats, the k-th of which multiplies (x - x0)<sup>k</sup>), 'errors' (an array of floats whose values are the estimated absolute errors of the coefficients), 'polynomial' (a string which uses this app's syntax for functions), 'radius' (a float, or null if none was specified), and 'remainder' (a float, or null if no radius was specified).  An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub x0: f64,
+	pub order: i32,
+	pub coefficients: Vec<f64>,
+	pub errors: Vec<f64>,
+	pub polynomial: String,
+	pub radius: Option<f64>,
+	pub remainder: Option<f64>,
+}
+
+const SAMPLES: i32 = 20; // number of intervals within the radius at which to sample the derivative
+
+impl Results {
+	// Horner's method
+	pub fn evaluate(&self, x: f64) -> f64 {
+		self.coefficients.iter().rev().fold(0., |sum, coefficient| sum * (x - self.x0) + coefficient)
+	}
+}
+
+// the polynomial, written so that it may be pasted into the url of any page of this app
+fn polynomial(x0: f64, coefficients: &[f64]) -> String {
+	let base = if x0 == 0. {
+		"x".to_string()
+	} else if x0 > 0. {
+		format!("(x-{})", x0)
+	} else {
+		format!("(x+{})", -x0)
+	};
+	let mut text = "".to_string();
+	for (k, coefficient) in coefficients.iter().enumerate() {
+		if *coefficient == 0. {
+			continue;
+		}
+		let sign = if *coefficient < 0. {"-"} else if text.is_empty() {""} else {"+"};
+		let power = match k {
+			0 => "".to_string(),
+			1 => format!("*{}", base),
+			_ => format!("*{}**{}", base, k),
+		};
+		text = format!("{}{}{}{}", text, sign, coefficient.abs(), power);
+	}
+	if text.is_empty() {"0".to_string()} else {text}
+}
+
+pub fn raw(x0_str: &RawStr, order_str: &RawStr, input_str: &RawStr, radius_str: Option<&RawStr>) -> Result<Results, String> {
+	let x0 = match helper::parse_expression(x0_str.to_string()) {
+		Ok(x0) => x0,
+		Err(message) => return Err(message),
+	};
+	let order = match helper::parse_expression(order_str.to_string()) {
+		Ok(order) => {
+			// The remainder requires one more derivative than the polynomial does.
+			if order.round() != order {
+				return Err(format!("{} is not an integer.", order));
+			} else if order < 0. || order > (differentiation::MAX_ORDER - 1) as f64 {
+				return Err(format!("The order must be between 0 and {}.", differentiation::MAX_ORDER - 1));
+			}
+			order as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let radius = match radius_str {
+		None => None,
+		Some(radius_str) => match helper::parse_expression(radius_str.to_string()) {
+			Ok(radius) => {
+				if radius <= 0. {
+					return Err(format!("The radius ({}) must be positive.", radius));
+				}
+				Some(radius)
+			},
+			Err(message) => return Err(message),
+		},
+	};
+	let function = |x| helper::function1(input_str.to_string(), x);
+	let results = match differentiation::derivatives(&function, x0, order) {
+		Ok(results) => results,
+		Err(message) => return Err(message),
+	};
+	let mut factorial = 1.;
+	let mut coefficients = vec![];
+	let mut errors = vec![];
+	for (k, (deriv, error)) in results.derivs.iter().zip(&results.errors).enumerate() {
+		if k > 0 {
+			factorial *= k as f64;
+		}
+		coefficients.push(deriv / factorial);
+		errors.push(error / factorial);
+	}
+	let remainder = match radius {
+		None => None,
+		Some(radius) => {
+			let next = order + 1;
+			let stencil = differentiation::central(next, true);
+			let mut max: f64 = 0.;
+			for i in 0..=SAMPLES {
+				let x = x0 - radius + 2. * radius * (i as f64) / (SAMPLES as f64);
+				match differentiation::differentiate(&|h| differentiation::apply(&function, x, &stencil, h), differentiation::step(x, next), 2) {
+					Ok((deriv, _)) => max = max.max(deriv.abs()),
+					Err(message) => return Err(format!("Error at x = {}: {}", x, message)),
+				}
+			}
+			Some(max * radius.powi(next) / (factorial * next as f64))
+		},
+	};
+	Ok(Results {
+		x0,
+		order,
+		polynomial: polynomial(x0, &coefficients),
+		coefficients,
+		errors,
+		radius,
+		remainder,
+	})
+}